@@ -0,0 +1,344 @@
+//! Headless entry point for running the security checks from a terminal,
+//! cron job or provisioning script.
+//!
+//! Exit codes: 0 when every check passes, 1 when a check fails or errors,
+//! 2 on invalid usage and 3 when the report could not be sent or fetched.
+
+use bruma_lib::security::{CheckContext, CheckOutcome, CheckStatus, Policy, PolicyStore, Registry};
+use bruma_lib::supabase::{self, SecurityReport};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage:
+  bruma-cli check [--format json|table] [--only <check-id>]... [--policy <path>]
+  bruma-cli report --email <email> --name <full name> [--format json|table] [--policy <path>]
+  bruma-cli last-report --email <email> [--format json|table]
+  bruma-cli list
+
+Checks run with the policy saved by the app unless --policy names another
+policy file.";
+
+/// The app's bundle identifier from `tauri.conf.json`, which names its
+/// config directory.
+const APP_IDENTIFIER: &str = "com.bruma.app";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Json,
+    Table,
+}
+
+enum Command {
+    Check { only: Vec<String> },
+    Report { email: String, name: String },
+    LastReport { email: String },
+    List,
+}
+
+struct Args {
+    command: Command,
+    format: Format,
+    policy: Option<PathBuf>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let subcommand = args.next().ok_or("Missing subcommand")?;
+
+    let mut format = Format::Table;
+    let mut only = Vec::new();
+    let mut email = None;
+    let mut name = None;
+    let mut policy = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--format" => {
+                format = match value()?.as_str() {
+                    "json" => Format::Json,
+                    "table" => Format::Table,
+                    other => return Err(format!("Unknown format: {}", other)),
+                }
+            }
+            "--only" => only.push(value()?),
+            "--email" => email = Some(value()?),
+            "--name" => name = Some(value()?),
+            "--policy" => policy = Some(PathBuf::from(value()?)),
+            other => return Err(format!("Unexpected argument: {}", other)),
+        }
+    }
+
+    let command = match subcommand.as_str() {
+        "check" => Command::Check { only },
+        "report" => Command::Report {
+            email: email.ok_or("report requires --email")?,
+            name: name.ok_or("report requires --name")?,
+        },
+        "last-report" => Command::LastReport {
+            email: email.ok_or("last-report requires --email")?,
+        },
+        "list" => Command::List,
+        other => return Err(format!("Unknown subcommand: {}", other)),
+    };
+
+    Ok(Args {
+        command,
+        format,
+        policy,
+    })
+}
+
+/// Reads the policy given with `--policy`, or the one the app saved in its
+/// config directory. Without a saved policy the defaults apply, but a policy
+/// file named on the command line must exist and be valid.
+fn load_policy(path: Option<&Path>) -> Result<Policy, String> {
+    match path {
+        Some(path) => {
+            let contents = std::fs::read_to_string(path)
+                .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+            serde_json::from_str(&contents)
+                .map_err(|e| format!("Invalid policy in {}: {}", path.display(), e))
+        }
+        None => Ok(dirs::config_dir()
+            .map(|dir| PolicyStore::load(dir.join(APP_IDENTIFIER).join("policy.json")).policy())
+            .unwrap_or_default()),
+    }
+}
+
+async fn run_checks(
+    registry: &Registry,
+    only: &[String],
+    policy: Policy,
+) -> BTreeMap<String, CheckOutcome> {
+    let ctx = CheckContext::default().with_policy(policy);
+    if only.is_empty() {
+        return registry.run_all(&ctx).await;
+    }
+
+    let mut outcomes = BTreeMap::new();
+    for id in only {
+        let outcome = registry
+            .run(id, &ctx)
+            .await
+            .unwrap_or_else(|| CheckOutcome::error(format!("Unknown security check: {}", id)));
+        outcomes.insert(id.clone(), outcome);
+    }
+    outcomes
+}
+
+fn status_label(status: CheckStatus) -> &'static str {
+    match status {
+        CheckStatus::Pass => "PASS",
+        CheckStatus::Fail => "FAIL",
+        CheckStatus::Warn => "WARN",
+        CheckStatus::Error => "ERROR",
+        CheckStatus::NotApplicable => "N/A",
+    }
+}
+
+fn print_outcomes(outcomes: &BTreeMap<String, CheckOutcome>, format: Format) {
+    if format == Format::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(outcomes).unwrap_or_default()
+        );
+        return;
+    }
+
+    let width = outcomes
+        .keys()
+        .map(String::len)
+        .max()
+        .unwrap_or(0)
+        .max("CHECK".len());
+    println!(
+        "{:<width$}  {:<6}  MESSAGE",
+        "CHECK",
+        "STATUS",
+        width = width
+    );
+    for (id, outcome) in outcomes {
+        println!(
+            "{:<width$}  {:<6}  {}",
+            id,
+            status_label(outcome.status),
+            outcome.message,
+            width = width
+        );
+        if let Some(remediation) = outcome
+            .remediation
+            .as_ref()
+            .filter(|_| !outcome.is_compliant())
+        {
+            println!(
+                "{:<width$}  {:<6}  -> {}",
+                "",
+                "",
+                remediation,
+                width = width
+            );
+        }
+    }
+}
+
+fn checks_exit_code(outcomes: &BTreeMap<String, CheckOutcome>) -> ExitCode {
+    let failed = outcomes
+        .values()
+        .any(|outcome| matches!(outcome.status, CheckStatus::Fail | CheckStatus::Error));
+
+    if failed {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+async fn run(args: Args) -> ExitCode {
+    let registry = Registry::builtin();
+    let policy = match load_policy(args.policy.as_deref()) {
+        Ok(policy) => policy,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    match args.command {
+        Command::List => {
+            let checks: Vec<_> = registry.iter().map(|check| check.info()).collect();
+            if args.format == Format::Json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&checks).unwrap_or_default()
+                );
+            } else {
+                for check in checks {
+                    println!("{:<20}  {}", check.id, check.title);
+                }
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Check { only } => {
+            let outcomes = run_checks(&registry, &only, policy).await;
+            print_outcomes(&outcomes, args.format);
+            checks_exit_code(&outcomes)
+        }
+        Command::Report { email, name } => {
+            let outcomes = run_checks(&registry, &[], policy).await;
+            print_outcomes(&outcomes, args.format);
+            let exit_code = checks_exit_code(&outcomes);
+
+            let report = SecurityReport { checks: outcomes };
+            match supabase::send_report(email, name, report).await {
+                Ok(()) => {
+                    eprintln!("Report sent");
+                    exit_code
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    ExitCode::from(3)
+                }
+            }
+        }
+        Command::LastReport { email } => match supabase::fetch_last_report(&email).await {
+            Ok(Some(report)) => {
+                let value = serde_json::to_value(&report).unwrap_or_default();
+                if args.format == Format::Json {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&value).unwrap_or_default()
+                    );
+                } else if let Some(fields) = value.as_object() {
+                    for (key, value) in fields.iter().filter(|(key, _)| *key != "checks") {
+                        println!("{:<20}  {}", key, value);
+                    }
+                }
+                ExitCode::SUCCESS
+            }
+            Ok(None) => {
+                eprintln!("No report has been sent from this device");
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                ExitCode::from(3)
+            }
+        },
+    }
+}
+
+fn main() -> ExitCode {
+    if std::env::args()
+        .nth(1)
+        .is_some_and(|arg| arg == "--help" || arg == "help")
+    {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    tauri::async_runtime::block_on(run(args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> impl Iterator<Item = String> {
+        line.split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn parses_check_with_filters() {
+        let parsed = parse_args(args(
+            "check --format json --only antivirus --only screen_lock",
+        ))
+        .unwrap();
+
+        assert!(parsed.format == Format::Json);
+        assert!(
+            matches!(parsed.command, Command::Check { only } if only == ["antivirus", "screen_lock"])
+        );
+    }
+
+    #[test]
+    fn report_requires_identity() {
+        assert!(parse_args(args("report --email a@b.c")).is_err());
+        assert!(parse_args(args("report --email a@b.c --name Ada")).is_ok());
+        assert!(parse_args(args("check --format yaml")).is_err());
+        assert!(parse_args(args("check --only")).is_err());
+    }
+
+    #[test]
+    fn policy_file_overrides_the_saved_policy() {
+        let parsed = parse_args(args("check --policy /etc/bruma/policy.json")).unwrap();
+        assert_eq!(
+            parsed.policy.as_deref(),
+            Some(Path::new("/etc/bruma/policy.json"))
+        );
+
+        let path = std::env::temp_dir().join("bruma-cli-policy.json");
+        std::fs::write(&path, r#"{"max_update_age_days": 14}"#).unwrap();
+        let policy = load_policy(Some(&path)).unwrap();
+        assert_eq!(policy.max_update_age_days, 14);
+        assert_eq!(policy.clamav_max_signature_age_days, 7);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load_policy(Some(&path)).is_err());
+        assert!(load_policy(Some(Path::new("/nonexistent/policy.json"))).is_err());
+    }
+}
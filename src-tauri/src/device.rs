@@ -1,18 +1,41 @@
-#[cfg(target_os = "windows")]
-use {std::os::windows::process::CommandExt, std::process::Command};
+use crate::security::{CommandRunner, SystemRunner};
 
-#[cfg(target_os = "macos")]
-use std::process::Command;
+pub fn get_device_id() -> String {
+    device_id(&SystemRunner)
+}
 
 #[cfg(target_os = "windows")]
-pub fn get_device_id() -> String {
-    let output = Command::new("wmic")
-        .args(["os", "get", "serialnumber"])
-        .creation_flags(0x08000000)
-        .output()
+fn device_id(runner: &dyn CommandRunner) -> String {
+    let output = runner
+        .run("wmic", &["os", "get", "serialnumber"])
         .expect("Failed to execute wmic command");
 
-    let uuid = String::from_utf8_lossy(&output.stdout)
+    parse_wmic_serial(&output.stdout)
+}
+
+#[cfg(target_os = "macos")]
+fn device_id(runner: &dyn CommandRunner) -> String {
+    let output = runner
+        .run("system_profiler", &["SPHardwareDataType"])
+        .expect("Failed to execute system_profiler command");
+
+    parse_system_profiler_serial(&output.stdout)
+}
+
+#[cfg(target_os = "linux")]
+fn device_id(_runner: &dyn CommandRunner) -> String {
+    if let Ok(machine_id) = std::fs::read_to_string("/etc/machine-id") {
+        machine_id.trim().to_string()
+    } else if let Ok(dmi_id) = std::fs::read_to_string("/sys/class/dmi/id/product_uuid") {
+        dmi_id.trim().to_string()
+    } else {
+        "unknown".to_string()
+    }
+}
+
+#[cfg(any(target_os = "windows", test))]
+fn parse_wmic_serial(output: &str) -> String {
+    let uuid = output
         .lines()
         .nth(1)
         .unwrap_or("unknown")
@@ -26,15 +49,9 @@ pub fn get_device_id() -> String {
     }
 }
 
-#[cfg(target_os = "macos")]
-pub fn get_device_id() -> String {
-    let output = Command::new("system_profiler")
-        .args(["SPHardwareDataType"])
-        .output()
-        .expect("Failed to execute system_profiler command");
-
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    output_str
+#[cfg(any(target_os = "macos", test))]
+fn parse_system_profiler_serial(output: &str) -> String {
+    output
         .lines()
         .find(|line| line.contains("Serial Number"))
         .and_then(|line| line.split_whitespace().nth(3))
@@ -42,13 +59,25 @@ pub fn get_device_id() -> String {
         .to_string()
 }
 
-#[cfg(target_os = "linux")]
-pub fn get_device_id() -> String {
-    if let Ok(machine_id) = std::fs::read_to_string("/etc/machine-id") {
-        machine_id.trim().to_string()
-    } else if let Ok(dmi_id) = std::fs::read_to_string("/sys/class/dmi/id/product_uuid") {
-        dmi_id.trim().to_string()
-    } else {
-        "unknown".to_string()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_wmic_serial_number() {
+        let output = "SerialNumber  \r\n00330-80000-00000-AA123  \r\n\r\n";
+        assert_eq!(parse_wmic_serial(output), "00330-80000-00000-AA123");
+    }
+
+    #[test]
+    fn missing_wmic_serial_number_is_unknown() {
+        assert_eq!(parse_wmic_serial("SerialNumber\r\n\r\n"), "unknown");
+        assert_eq!(parse_wmic_serial(""), "unknown");
+    }
+
+    #[test]
+    fn parses_system_profiler_serial_number() {
+        let output = include_str!("security/fixtures/macos/system_profiler_hardware.txt");
+        assert_eq!(parse_system_profiler_serial(output), "C02XK1ZZJGH5");
     }
 }
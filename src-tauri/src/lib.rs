@@ -1,8 +1,10 @@
 use tauri::Manager;
 mod commands;
 mod device;
-mod security;
-mod supabase;
+mod outbox;
+mod scheduler;
+pub mod security;
+pub mod supabase;
 mod supabase_credentials;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -15,8 +17,31 @@ pub fn run() {
                 .expect("could not resolve app local data path")
                 .join("salt.txt");
             app.handle().plugin(tauri_plugin_stronghold::Builder::with_argon2(&salt_path).build())?;
+
+            let outbox_path = app
+                .path()
+                .app_local_data_dir()
+                .expect("could not resolve app local data path")
+                .join("outbox.json");
+            app.manage(outbox::Outbox::load(outbox_path));
+
+            let schedule_path = app
+                .path()
+                .app_config_dir()
+                .expect("could not resolve app config path")
+                .join("schedule.json");
+            app.manage(scheduler::Scheduler::load(schedule_path));
+
+            let policy_path = app
+                .path()
+                .app_config_dir()
+                .expect("could not resolve app config path")
+                .join("policy.json");
+            app.manage(security::PolicyStore::load(policy_path));
+            scheduler::start(app.handle());
             Ok(())
         })
+        .manage(security::Registry::builtin())
         .plugin(tauri_plugin_window_state::Builder::new().build())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
@@ -28,9 +53,14 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .invoke_handler(tauri::generate_handler![
             commands::get_device_info,
-            security::get_antivirus_info,
-            security::get_disk_encryption_info,
-            security::get_screen_lock_info,
+            outbox::get_pending_reports,
+            scheduler::get_schedule_config,
+            scheduler::set_schedule_config,
+            scheduler::set_report_identity,
+            security::get_policy,
+            security::list_checks,
+            security::run_check,
+            security::set_policy,
             supabase::send_security_report,
             supabase::get_last_report,
             supabase_credentials::has_supabase_credentials,
@@ -0,0 +1,365 @@
+use crate::supabase::{self, DeliveryError, SupabaseReport};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::PathBuf;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Delay before the first retry, doubled after every further failure.
+const BASE_BACKOFF_SECS: i64 = 60;
+const MAX_BACKOFF_SECS: i64 = 6 * 60 * 60;
+
+/// The most reports kept in `outbox.json`. Each report replaces the
+/// device's row, so when the queue overflows the oldest ones are dropped
+/// first, after any the server rejected.
+const MAX_PENDING: usize = 50;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PendingReport {
+    pub report: SupabaseReport,
+    pub attempts: u32,
+    /// Unix timestamp before which the report is not retried.
+    pub next_attempt: i64,
+    pub last_error: Option<String>,
+    /// Set when the server refused the report. It is kept for inspection
+    /// but never retried, so it cannot block the reports behind it.
+    #[serde(default)]
+    pub rejected: bool,
+}
+
+/// Why `Outbox::send` did not deliver a report.
+#[derive(Debug, PartialEq)]
+pub enum SendError {
+    /// Delivery failed for now, or earlier reports are still waiting; the
+    /// report is queued and retried later.
+    Queued(String),
+    /// The report was rejected or delivery is not configured, so it will
+    /// not be retried.
+    Failed(String),
+}
+
+/// Reports that could not be delivered yet, persisted so they survive a
+/// restart. They are delivered oldest first so a stale report never
+/// overwrites a newer one.
+pub struct Outbox {
+    path: PathBuf,
+    pending: Mutex<Vec<PendingReport>>,
+}
+
+impl Outbox {
+    pub fn load(path: PathBuf) -> Self {
+        let pending = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Outbox {
+            path,
+            pending: Mutex::new(pending),
+        }
+    }
+
+    pub async fn pending(&self) -> Vec<PendingReport> {
+        self.pending.lock().await.clone()
+    }
+
+    /// Queues a report and immediately tries to deliver it along with
+    /// anything queued before it, ignoring their backoff. A report that
+    /// failed transiently stays queued; one that cannot be sent is not.
+    pub async fn send(&self, report: SupabaseReport) -> Result<(), SendError> {
+        self.send_with(report, chrono::Utc::now().timestamp(), supabase::deliver)
+            .await
+    }
+
+    /// Retries the reports whose backoff has elapsed, returning how many
+    /// were delivered.
+    pub async fn flush(&self) -> Result<usize, String> {
+        self.flush_with(chrono::Utc::now().timestamp(), supabase::deliver)
+            .await
+    }
+
+    async fn send_with<F, Fut>(
+        &self,
+        report: SupabaseReport,
+        now: i64,
+        deliver: F,
+    ) -> Result<(), SendError>
+    where
+        F: Fn(SupabaseReport) -> Fut,
+        Fut: Future<Output = Result<(), DeliveryError>>,
+    {
+        let mut pending = self.pending.lock().await;
+        let key = report.key();
+        let queued = !pending.iter().any(|entry| entry.report.key() == key);
+        if queued {
+            pending.push(PendingReport {
+                report: report.clone(),
+                attempts: 0,
+                next_attempt: now,
+                last_error: None,
+                rejected: false,
+            });
+        }
+
+        let (_, stopped_by) = deliver_due(&mut pending, now, true, deliver).await;
+        // Without configuration nothing can be sent, so don't keep a report
+        // the user was just told about
+        if let Some(DeliveryError::Configuration(e)) = stopped_by {
+            if queued {
+                pending.retain(|entry| entry.report.key() != report.key());
+            }
+            self.save(&pending).map_err(SendError::Failed)?;
+            return Err(SendError::Failed(e));
+        }
+        truncate(&mut pending);
+        self.save(&pending).map_err(SendError::Failed)?;
+
+        match pending
+            .iter()
+            .find(|entry| entry.report.key() == report.key())
+        {
+            Some(entry) if entry.rejected => Err(SendError::Failed(
+                entry.last_error.clone().unwrap_or_default(),
+            )),
+            Some(entry) => {
+                Err(SendError::Queued(entry.last_error.clone().unwrap_or_else(
+                    || "Waiting for earlier reports to be sent".to_string(),
+                )))
+            }
+            None => Ok(()),
+        }
+    }
+
+    async fn flush_with<F, Fut>(&self, now: i64, deliver: F) -> Result<usize, String>
+    where
+        F: Fn(SupabaseReport) -> Fut,
+        Fut: Future<Output = Result<(), DeliveryError>>,
+    {
+        let mut pending = self.pending.lock().await;
+        if pending.iter().all(|entry| entry.rejected) {
+            return Ok(0);
+        }
+
+        let (delivered, _) = deliver_due(&mut pending, now, false, deliver).await;
+        self.save(&pending)?;
+        Ok(delivered)
+    }
+
+    fn save(&self, pending: &[PendingReport]) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let serialized = serde_json::to_string(pending).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, serialized).map_err(|e| e.to_string())
+    }
+}
+
+/// Delivers queued reports in order, stopping at the first failure that
+/// may go away. Rejected reports are parked and skipped. Returns how many
+/// were delivered and the error that stopped delivery, if any.
+async fn deliver_due<F, Fut>(
+    pending: &mut Vec<PendingReport>,
+    now: i64,
+    force: bool,
+    deliver: F,
+) -> (usize, Option<DeliveryError>)
+where
+    F: Fn(SupabaseReport) -> Fut,
+    Fut: Future<Output = Result<(), DeliveryError>>,
+{
+    let mut delivered = 0;
+    let mut index = 0;
+    while let Some(entry) = pending.get_mut(index) {
+        if entry.rejected {
+            index += 1;
+            continue;
+        }
+        if !force && entry.next_attempt > now {
+            break;
+        }
+
+        match deliver(entry.report.clone()).await {
+            Ok(()) => {
+                pending.remove(index);
+                delivered += 1;
+            }
+            Err(DeliveryError::Rejected(e)) => {
+                entry.attempts += 1;
+                entry.last_error = Some(e);
+                entry.rejected = true;
+                index += 1;
+            }
+            Err(e) => {
+                // Configuration errors are not the report's fault, so they
+                // don't count towards its backoff
+                if let DeliveryError::Transient(_) = e {
+                    entry.attempts += 1;
+                    entry.next_attempt = now + backoff(entry.attempts);
+                }
+                entry.last_error = Some(e.to_string());
+                return (delivered, Some(e));
+            }
+        }
+    }
+    (delivered, None)
+}
+
+/// Keeps the outbox at `MAX_PENDING` reports, dropping rejected reports
+/// before the oldest pending ones.
+fn truncate(pending: &mut Vec<PendingReport>) {
+    while pending.len() > MAX_PENDING {
+        let index = pending.iter().position(|entry| entry.rejected).unwrap_or(0);
+        pending.remove(index);
+    }
+}
+
+fn backoff(attempts: u32) -> i64 {
+    let doublings = attempts.saturating_sub(1).min(16);
+    (BASE_BACKOFF_SECS << doublings).min(MAX_BACKOFF_SECS)
+}
+
+#[tauri::command]
+pub async fn get_pending_reports(outbox: State<'_, Outbox>) -> Result<Vec<PendingReport>, String> {
+    Ok(outbox.pending().await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::supabase::{SecurityReport, build_report};
+
+    fn report() -> SupabaseReport {
+        build_report(
+            "ada@example.com".to_string(),
+            "Ada".to_string(),
+            SecurityReport::default(),
+        )
+    }
+
+    fn outbox(name: &str) -> Outbox {
+        let path = std::env::temp_dir().join(format!("bruma-outbox-{}.json", name));
+        let _ = std::fs::remove_file(&path);
+        Outbox::load(path)
+    }
+
+    async fn offline(_: SupabaseReport) -> Result<(), DeliveryError> {
+        Err(DeliveryError::Transient(
+            "error sending request".to_string(),
+        ))
+    }
+
+    async fn online(_: SupabaseReport) -> Result<(), DeliveryError> {
+        Ok(())
+    }
+
+    async fn rejecting(_: SupabaseReport) -> Result<(), DeliveryError> {
+        Err(DeliveryError::Rejected(
+            "Failed to send report: unknown column".to_string(),
+        ))
+    }
+
+    async fn unconfigured(_: SupabaseReport) -> Result<(), DeliveryError> {
+        Err(DeliveryError::Configuration(
+            "Supabase credentials not configured".to_string(),
+        ))
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_a_cap() {
+        assert_eq!(backoff(1), 60);
+        assert_eq!(backoff(2), 120);
+        assert_eq!(backoff(3), 240);
+        assert_eq!(backoff(30), MAX_BACKOFF_SECS);
+    }
+
+    #[test]
+    fn failed_reports_are_queued_once_and_persisted() {
+        tauri::async_runtime::block_on(async {
+            let outbox = outbox("queued");
+            let report = report();
+
+            assert_eq!(
+                outbox.send_with(report.clone(), 1000, offline).await,
+                Err(SendError::Queued("error sending request".to_string()))
+            );
+            assert!(outbox.send_with(report, 1000, offline).await.is_err());
+
+            let reloaded = Outbox::load(outbox.path.clone());
+            let pending = reloaded.pending().await;
+            assert_eq!(pending.len(), 1);
+            assert_eq!(pending[0].attempts, 2);
+            assert_eq!(pending[0].next_attempt, 1000 + 120);
+            assert_eq!(
+                pending[0].last_error.as_deref(),
+                Some("error sending request")
+            );
+        });
+    }
+
+    #[test]
+    fn flush_waits_for_backoff_then_delivers_in_order() {
+        tauri::async_runtime::block_on(async {
+            let outbox = outbox("flush");
+
+            assert!(outbox.send_with(report(), 1000, offline).await.is_err());
+            assert!(outbox.send_with(report(), 1000, offline).await.is_err());
+            assert_eq!(outbox.pending().await.len(), 2);
+
+            assert_eq!(outbox.flush_with(1030, online).await, Ok(0));
+            assert_eq!(outbox.flush_with(1120, online).await, Ok(2));
+            assert!(outbox.pending().await.is_empty());
+        });
+    }
+
+    #[test]
+    fn rejected_reports_are_parked_behind_later_ones() {
+        tauri::async_runtime::block_on(async {
+            let outbox = outbox("rejected");
+
+            assert_eq!(
+                outbox.send_with(report(), 1000, rejecting).await,
+                Err(SendError::Failed(
+                    "Failed to send report: unknown column".to_string()
+                ))
+            );
+            assert!(outbox.send_with(report(), 1000, offline).await.is_err());
+
+            assert_eq!(outbox.flush_with(1060, online).await, Ok(1));
+            let pending = outbox.pending().await;
+            assert_eq!(pending.len(), 1);
+            assert!(pending[0].rejected);
+            assert_eq!(outbox.flush_with(2000, online).await, Ok(0));
+        });
+    }
+
+    #[test]
+    fn missing_configuration_is_not_queued() {
+        tauri::async_runtime::block_on(async {
+            let outbox = outbox("unconfigured");
+
+            assert_eq!(
+                outbox.send_with(report(), 1000, unconfigured).await,
+                Err(SendError::Failed(
+                    "Supabase credentials not configured".to_string()
+                ))
+            );
+            assert!(outbox.pending().await.is_empty());
+        });
+    }
+
+    #[test]
+    fn queue_is_capped() {
+        tauri::async_runtime::block_on(async {
+            let outbox = outbox("capped");
+
+            assert!(outbox.send_with(report(), 1000, rejecting).await.is_err());
+            for _ in 0..MAX_PENDING {
+                assert!(outbox.send_with(report(), 1000, offline).await.is_err());
+            }
+
+            let pending = outbox.pending().await;
+            assert_eq!(pending.len(), MAX_PENDING);
+            assert!(pending.iter().all(|entry| !entry.rejected));
+        });
+    }
+}
@@ -0,0 +1,275 @@
+use crate::outbox::{Outbox, SendError};
+use crate::security::{CheckContext, CheckOutcome, PolicyStore, Registry};
+use crate::supabase::{self, SecurityReport};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::Notify;
+
+/// How often the scheduler wakes up to see whether a run is due.
+const TICK: Duration = Duration::from_secs(60);
+
+/// A wall-clock gap between two ticks larger than this means the machine
+/// was suspended in between.
+const WAKE_THRESHOLD: Duration = Duration::from_secs(5 * 60);
+
+/// The longest interval between scheduled runs, 30 days.
+const MAX_INTERVAL_HOURS: u64 = 24 * 30;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ScheduleConfig {
+    pub enabled: bool,
+    pub interval_hours: u64,
+    pub user_email: String,
+    pub user_full_name: String,
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        ScheduleConfig {
+            enabled: true,
+            interval_hours: 24,
+            user_email: String::new(),
+            user_full_name: String::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+struct CheckCompleted<'a> {
+    id: &'a str,
+    outcome: &'a CheckOutcome,
+}
+
+pub struct Scheduler {
+    path: PathBuf,
+    config: Mutex<ScheduleConfig>,
+    wake: Notify,
+}
+
+impl Scheduler {
+    pub fn load(path: PathBuf) -> Self {
+        let config = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Scheduler {
+            path,
+            config: Mutex::new(config),
+            wake: Notify::new(),
+        }
+    }
+
+    pub fn config(&self) -> ScheduleConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    fn update(&self, change: impl FnOnce(&mut ScheduleConfig)) -> Result<ScheduleConfig, String> {
+        let config = {
+            let mut config = self.config.lock().unwrap();
+            change(&mut config);
+            config.clone()
+        };
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let serialized = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, serialized).map_err(|e| e.to_string())?;
+
+        self.wake.notify_one();
+        Ok(config)
+    }
+}
+
+#[tauri::command]
+pub async fn get_schedule_config(
+    scheduler: State<'_, Scheduler>,
+) -> Result<ScheduleConfig, String> {
+    Ok(scheduler.config())
+}
+
+#[tauri::command]
+pub async fn set_schedule_config(
+    scheduler: State<'_, Scheduler>,
+    enabled: bool,
+    interval_hours: u64,
+) -> Result<ScheduleConfig, String> {
+    if interval_hours == 0 {
+        return Err("The interval must be at least one hour".to_string());
+    }
+    if interval_hours > MAX_INTERVAL_HOURS {
+        return Err(format!(
+            "The interval must be at most {} hours",
+            MAX_INTERVAL_HOURS
+        ));
+    }
+
+    scheduler.update(|config| {
+        config.enabled = enabled;
+        config.interval_hours = interval_hours;
+    })
+}
+
+#[tauri::command]
+pub async fn set_report_identity(
+    scheduler: State<'_, Scheduler>,
+    user_email: String,
+    user_full_name: String,
+) -> Result<ScheduleConfig, String> {
+    scheduler.update(|config| {
+        config.user_email = user_email;
+        config.user_full_name = user_full_name;
+    })
+}
+
+/// Whether a run is due, either because the interval has elapsed since the
+/// last one or because the machine just resumed from sleep. `last_tick` is
+/// when the previous tick finished its work, so a slow run is not mistaken
+/// for a suspend.
+fn is_due(
+    config: &ScheduleConfig,
+    now: SystemTime,
+    last_tick: SystemTime,
+    last_run: SystemTime,
+) -> bool {
+    // The config file may have been edited by hand, so clamp it here too
+    let hours = config.interval_hours.clamp(1, MAX_INTERVAL_HOURS);
+    let interval = Duration::from_secs(hours * 60 * 60);
+    let since = |earlier: SystemTime| now.duration_since(earlier).unwrap_or_default();
+
+    since(last_tick) > WAKE_THRESHOLD || since(last_run) >= interval
+}
+
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move { run_loop(app).await });
+}
+
+async fn run_loop(app: AppHandle) {
+    let scheduler = app.state::<Scheduler>();
+    let outbox = app.state::<Outbox>();
+
+    // Starting the app counts as a login, so the first tick always runs
+    let mut due = true;
+    let mut last_run = SystemTime::now();
+    let mut last_tick = SystemTime::now();
+
+    loop {
+        let config = scheduler.config();
+        let now = SystemTime::now();
+        due |= is_due(&config, now, last_tick, last_run);
+
+        if due && config.enabled {
+            run_scheduled(&app, &config).await;
+            last_run = SystemTime::now();
+            due = false;
+        } else if outbox.flush().await.is_ok_and(|delivered| delivered > 0) {
+            let _ = app.emit("security-report-sent", ());
+        }
+        last_tick = SystemTime::now();
+
+        tokio::select! {
+            _ = tokio::time::sleep(TICK) => {}
+            _ = scheduler.wake.notified() => {}
+        }
+    }
+}
+
+/// Runs every check, emitting progress events for the UI, and sends the
+/// report through the outbox once the user has identified themselves.
+async fn run_scheduled(app: &AppHandle, config: &ScheduleConfig) {
+    let registry = app.state::<Registry>();
+    let ctx = CheckContext::default().with_policy(app.state::<PolicyStore>().policy());
+
+    let _ = app.emit("security-checks-started", ());
+    let mut checks = BTreeMap::new();
+    for check in registry.iter() {
+        let outcome = check.run(&ctx).await;
+        let _ = app.emit(
+            "security-check-completed",
+            CheckCompleted {
+                id: check.id(),
+                outcome: &outcome,
+            },
+        );
+        checks.insert(check.id().to_string(), outcome);
+    }
+    let _ = app.emit("security-checks-finished", ());
+
+    if config.user_email.is_empty() {
+        return;
+    }
+
+    let report = supabase::build_report(
+        config.user_email.clone(),
+        config.user_full_name.clone(),
+        SecurityReport { checks },
+    );
+    match app.state::<Outbox>().send(report).await {
+        Ok(()) => {
+            let _ = app.emit("security-report-sent", ());
+        }
+        Err(SendError::Queued(e) | SendError::Failed(e)) => {
+            let _ = app.emit("security-report-failed", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutes(n: u64) -> Duration {
+        Duration::from_secs(n * 60)
+    }
+
+    #[test]
+    fn runs_when_interval_elapses() {
+        let config = ScheduleConfig::default();
+        let start = SystemTime::UNIX_EPOCH + minutes(60 * 24 * 365);
+
+        let soon = start + minutes(60);
+        assert!(!is_due(&config, soon, soon - TICK, start));
+
+        let later = start + minutes(24 * 60);
+        assert!(is_due(&config, later, later - TICK, start));
+    }
+
+    #[test]
+    fn runs_after_resuming_from_sleep() {
+        let config = ScheduleConfig::default();
+        let start = SystemTime::UNIX_EPOCH + minutes(60 * 24 * 365);
+
+        let resumed = start + minutes(90);
+        assert!(is_due(&config, resumed, start + minutes(10), start));
+    }
+
+    #[test]
+    fn oversized_interval_is_clamped() {
+        let config = ScheduleConfig {
+            interval_hours: u64::MAX,
+            ..ScheduleConfig::default()
+        };
+        let start = SystemTime::UNIX_EPOCH + minutes(60 * 24 * 365);
+
+        let later = start + minutes(60 * MAX_INTERVAL_HOURS);
+        assert!(!is_due(&config, later - TICK, later - TICK * 2, start));
+        assert!(is_due(&config, later, later - TICK, start));
+    }
+
+    #[test]
+    fn missing_config_falls_back_to_defaults() {
+        let scheduler = Scheduler::load(PathBuf::from("/nonexistent/schedule.json"));
+        let config = scheduler.config();
+
+        assert!(config.enabled);
+        assert_eq!(config.interval_hours, 24);
+        assert!(config.user_email.is_empty());
+    }
+}
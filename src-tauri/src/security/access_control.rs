@@ -0,0 +1,220 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use serde::{Deserialize, Serialize};
+#[cfg(any(target_os = "linux", test))]
+use std::io::ErrorKind;
+
+pub struct AccessControlCheck;
+
+/// The mandatory access control modules the kernel runs with.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct AccessControlStatus {
+    /// Active Linux security modules, in the order the kernel lists them.
+    pub lsm: Vec<String>,
+    pub selinux: Option<String>,
+    pub apparmor_enforced: Option<u32>,
+    pub apparmor_complain: Option<u32>,
+}
+
+#[async_trait]
+impl SecurityCheck for AccessControlCheck {
+    fn id(&self) -> &'static str {
+        "mandatory_access_control"
+    }
+
+    fn title(&self) -> &'static str {
+        "Mandatory Access Control Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying SELinux or AppArmor is enforcing"
+    }
+
+    fn category(&self) -> Category {
+        Category::Access
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Mandatory access control detection is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+const LSM: &str = "/sys/kernel/security/lsm";
+#[cfg(any(target_os = "linux", test))]
+const SELINUX_ENFORCE: &str = "/sys/fs/selinux/enforce";
+#[cfg(any(target_os = "linux", test))]
+const APPARMOR_PROFILES: &str = "/sys/kernel/security/apparmor/profiles";
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let mut status = AccessControlStatus::default();
+    let mut evidence = Vec::new();
+
+    // Kernels before 5.1 have no lsm list, so fall back to what is mounted
+    match ctx.read_file(LSM) {
+        Ok(lsm) => {
+            status.lsm = lsm.trim().split(',').map(str::to_string).collect();
+            evidence.push(Evidence::file(LSM, &[lsm.trim().to_string()]));
+        }
+        Err(_) => {
+            for (name, path) in [
+                ("selinux", "/sys/fs/selinux"),
+                ("apparmor", "/sys/kernel/security/apparmor"),
+            ] {
+                if ctx.path(path).exists() {
+                    status.lsm.push(name.to_string());
+                }
+            }
+        }
+    }
+    let enabled = |name: &str| status.lsm.iter().any(|lsm| lsm == name);
+
+    if enabled("selinux") {
+        let enforce = ctx.read_file(SELINUX_ENFORCE).map_err(|e| {
+            CheckOutcome::error(format!("Could not read {}: {}", SELINUX_ENFORCE, e))
+        })?;
+        evidence.push(Evidence::file(
+            SELINUX_ENFORCE,
+            &[enforce.trim().to_string()],
+        ));
+        status.selinux = Some(match enforce.trim() {
+            "1" => "enforcing".to_string(),
+            _ => "permissive".to_string(),
+        });
+    }
+
+    let mut apparmor_unreadable = false;
+    if enabled("apparmor") {
+        match ctx.read_file(APPARMOR_PROFILES) {
+            Ok(profiles) => {
+                let (enforced, complain) = count_apparmor_profiles(&profiles);
+                status.apparmor_enforced = Some(enforced);
+                status.apparmor_complain = Some(complain);
+                evidence.push(Evidence::file(
+                    APPARMOR_PROFILES,
+                    &[format!("{} enforce, {} complain", enforced, complain)],
+                ));
+            }
+            // Listing the profiles needs root
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => apparmor_unreadable = true,
+            Err(e) => {
+                return Err(CheckOutcome::error(format!(
+                    "Could not read {}: {}",
+                    APPARMOR_PROFILES, e
+                )));
+            }
+        }
+    }
+
+    let outcome = match (&status.selinux, status.apparmor_enforced) {
+        (Some(mode), _) if mode == "enforcing" => {
+            CheckOutcome::pass("SELinux is enforcing").with_value("SELinux")
+        }
+        (_, Some(enforced)) if enforced > 0 => CheckOutcome::pass(format!(
+            "AppArmor is enforcing {} profiles ({} in complain mode)",
+            enforced,
+            status.apparmor_complain.unwrap_or(0)
+        ))
+        .with_value("AppArmor"),
+        _ if apparmor_unreadable => {
+            CheckOutcome::warn("AppArmor is enabled, but its profiles can only be listed as root")
+                .with_value("AppArmor")
+        }
+        (Some(mode), _) => CheckOutcome::fail(format!("SELinux is in {} mode", mode))
+            .with_value("SELinux")
+            .with_remediation(
+                "Run `setenforce 1` and set SELINUX=enforcing in /etc/selinux/config",
+            ),
+        (_, Some(_)) => CheckOutcome::fail("AppArmor has no profiles in enforce mode")
+            .with_value("AppArmor")
+            .with_remediation("Load profiles in enforce mode with `aa-enforce`"),
+        (None, None) => CheckOutcome::fail("Neither SELinux nor AppArmor is enabled")
+            .with_remediation("Enable the SELinux or AppArmor module your distribution ships with"),
+    };
+
+    Ok(evidence
+        .into_iter()
+        .fold(outcome, CheckOutcome::with_evidence)
+        .with_details(&status))
+}
+
+/// Counts profiles in enforce and complain mode. Each line of the profiles
+/// file reads `name (mode)`.
+#[cfg(any(target_os = "linux", test))]
+fn count_apparmor_profiles(profiles: &str) -> (u32, u32) {
+    let mut counts = (0, 0);
+    for line in profiles.lines() {
+        match line.trim_end().rsplit_once(' ').map(|(_, mode)| mode) {
+            Some("(enforce)") | Some("(kill)") => counts.0 += 1,
+            Some("(complain)") => counts.1 += 1,
+            _ => {}
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    fn context(root: &str) -> CheckContext {
+        CheckContext::new(FixtureRunner::new()).with_root(fixture_root(root))
+    }
+
+    #[test]
+    fn selinux_enforcing_passes() {
+        let outcome = check_linux(&context("linux/mac_selinux")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("SELinux"));
+        assert_eq!(outcome.evidence[1].lines, ["1"]);
+    }
+
+    #[test]
+    fn apparmor_counts_profiles() {
+        let outcome = check_linux(&context("linux/mac_apparmor")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.message,
+            "AppArmor is enforcing 3 profiles (1 in complain mode)"
+        );
+
+        let status: AccessControlStatus =
+            serde_json::from_value(*outcome.details.unwrap()).unwrap();
+        assert_eq!(
+            status.lsm,
+            ["lockdown", "capability", "landlock", "yama", "apparmor"]
+        );
+    }
+
+    #[test]
+    fn no_security_module_fails() {
+        let outcome = check_linux(&context("linux/empty")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert!(outcome.remediation.is_some());
+    }
+}
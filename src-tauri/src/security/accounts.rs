@@ -0,0 +1,350 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use super::autologin::{self, LoginSetting};
+#[cfg(any(target_os = "linux", test))]
+use serde::{Deserialize, Serialize};
+
+pub struct AccountsCheck;
+
+/// Password policy and local accounts that allow logging in without it.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct AccountStatus {
+    /// Minimum password length, or `None` when no quality module is enabled.
+    pub min_length: Option<u32>,
+    /// Character class requirements, e.g. `minclass=3` or `dcredit=-1`.
+    pub complexity: Vec<String>,
+    /// `PASS_MAX_DAYS` from login.defs.
+    pub max_days: Option<u32>,
+    /// Accounts other than root with UID 0.
+    pub uid0_accounts: Vec<String>,
+    pub empty_passwords: Vec<String>,
+    pub shadow_readable: bool,
+    /// Display manager settings that log in without a password, as
+    /// `file: key=value`.
+    pub passwordless_logins: Vec<String>,
+}
+
+#[async_trait]
+impl SecurityCheck for AccountsCheck {
+    fn id(&self) -> &'static str {
+        "local_accounts"
+    }
+
+    fn title(&self) -> &'static str {
+        "Local Accounts Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying local accounts require a strong password"
+    }
+
+    fn category(&self) -> Category {
+        Category::Access
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Local account auditing is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+const PASSWD: &str = "/etc/passwd";
+#[cfg(any(target_os = "linux", test))]
+const SHADOW: &str = "/etc/shadow";
+#[cfg(any(target_os = "linux", test))]
+const LOGIN_DEFS: &str = "/etc/login.defs";
+#[cfg(any(target_os = "linux", test))]
+const PWQUALITY: &str = "/etc/security/pwquality.conf";
+
+/// The password stacks of Debian, Fedora and Arch derived distributions.
+#[cfg(any(target_os = "linux", test))]
+const PAM_PASSWORD: &[&str] = &[
+    "/etc/pam.d/common-password",
+    "/etc/pam.d/system-auth",
+    "/etc/pam.d/password-auth",
+];
+
+#[cfg(any(target_os = "linux", test))]
+const MIN_PASSWORD_LENGTH: u32 = 8;
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let passwd = ctx
+        .read_file(PASSWD)
+        .map_err(|e| CheckOutcome::error(format!("Could not read {}: {}", PASSWD, e)))?;
+    let mut status = AccountStatus::default();
+    let mut evidence = Vec::new();
+    let mut uid0_entries = Vec::new();
+
+    for entry in passwd.lines().filter(|line| !line.starts_with('#')) {
+        let fields: Vec<&str> = entry.split(':').collect();
+        let [name, password, uid, ..] = fields[..] else {
+            continue;
+        };
+        if uid == "0" && name != "root" {
+            status.uid0_accounts.push(name.to_string());
+            uid0_entries.push(entry.to_string());
+        }
+        if password.is_empty() {
+            status.empty_passwords.push(name.to_string());
+        }
+    }
+    if !uid0_entries.is_empty() {
+        evidence.push(Evidence::file(PASSWD, &uid0_entries));
+    }
+
+    // Only root can read the hashes
+    if let Ok(shadow) = ctx.read_file(SHADOW) {
+        status.shadow_readable = true;
+        for entry in shadow.lines() {
+            let mut fields = entry.split(':');
+            if let (Some(name), Some("")) = (fields.next(), fields.next())
+                && !status.empty_passwords.iter().any(|account| account == name)
+            {
+                status.empty_passwords.push(name.to_string());
+            }
+        }
+    }
+
+    if let Ok(login_defs) = ctx.read_file(LOGIN_DEFS) {
+        status.max_days = login_defs
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("PASS_MAX_DAYS"))
+            .find_map(|days| days.trim().parse().ok());
+    }
+
+    let quality = password_quality(ctx);
+    if let Some(quality) = &quality {
+        status.min_length = Some(quality.min_length);
+        status.complexity = quality.complexity.clone();
+        evidence.push(Evidence::file(&quality.source, &quality.lines));
+    }
+
+    status.passwordless_logins = autologin::passwordless_logins(ctx)
+        .iter()
+        .map(LoginSetting::to_string)
+        .collect();
+
+    let mut findings = Vec::new();
+    if !status.uid0_accounts.is_empty() {
+        findings.push(format!(
+            "accounts other than root have UID 0: {}",
+            status.uid0_accounts.join(", ")
+        ));
+    }
+    if !status.empty_passwords.is_empty() {
+        findings.push(format!(
+            "accounts have an empty password: {}",
+            status.empty_passwords.join(", ")
+        ));
+    }
+    if !status.passwordless_logins.is_empty() {
+        findings.push(format!(
+            "the display manager logs in without a password: {}",
+            status.passwordless_logins.join(", ")
+        ));
+    }
+
+    let mut warnings = Vec::new();
+    match status.min_length {
+        None => warnings.push("no password quality module is enabled".to_string()),
+        Some(length) if length < MIN_PASSWORD_LENGTH => warnings.push(format!(
+            "passwords only need {} characters, at least {} are recommended",
+            length, MIN_PASSWORD_LENGTH
+        )),
+        Some(_) => {}
+    }
+    if !status.shadow_readable {
+        warnings.push(format!("{} could only be read as root", SHADOW));
+    }
+
+    let outcome = if !findings.is_empty() {
+        CheckOutcome::fail(capitalize(&findings.join("; "))).with_remediation(
+            "Lock or remove the listed accounts with `passwd -l` and disable automatic and guest logins in the display manager",
+        )
+    } else if !warnings.is_empty() {
+        let outcome = CheckOutcome::warn(capitalize(&warnings.join("; ")));
+        if status
+            .min_length
+            .is_some_and(|length| length >= MIN_PASSWORD_LENGTH)
+        {
+            outcome
+        } else {
+            outcome.with_remediation(
+                "Enable pam_pwquality with minlen=12 in /etc/security/pwquality.conf",
+            )
+        }
+    } else {
+        CheckOutcome::pass(format!(
+            "Local accounts require a password of at least {} characters",
+            status.min_length.unwrap_or_default()
+        ))
+    };
+    let outcome = match status.min_length {
+        Some(length) => outcome.with_value(length.to_string()),
+        None => outcome,
+    };
+
+    Ok(evidence
+        .into_iter()
+        .fold(outcome, CheckOutcome::with_evidence)
+        .with_details(&status))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn capitalize(message: &str) -> String {
+    let mut chars = message.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+struct PasswordQuality {
+    min_length: u32,
+    complexity: Vec<String>,
+    source: String,
+    lines: Vec<String>,
+}
+
+/// The settings of pam_pwquality or pam_cracklib, when either is in the
+/// password stack. Module arguments override pwquality.conf.
+#[cfg(any(target_os = "linux", test))]
+fn password_quality(ctx: &CheckContext) -> Option<PasswordQuality> {
+    let (source, line) = PAM_PASSWORD.iter().find_map(|path| {
+        let stack = ctx.read_file(path).ok()?;
+        let line = stack.lines().map(str::trim).find(|line| {
+            !line.starts_with('#')
+                && line.split_whitespace().next() == Some("password")
+                && (line.contains("pam_pwquality.so") || line.contains("pam_cracklib.so"))
+        })?;
+        Some((path.to_string(), line.to_string()))
+    })?;
+
+    let mut settings = Vec::new();
+    if line.contains("pam_pwquality.so") {
+        let mut files = vec![PWQUALITY.to_string()];
+        let mut drop_ins: Vec<String> =
+            std::fs::read_dir(ctx.path("/etc/security/pwquality.conf.d"))
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| name.ends_with(".conf"))
+                .map(|name| format!("/etc/security/pwquality.conf.d/{}", name))
+                .collect();
+        drop_ins.sort();
+        files.extend(drop_ins);
+
+        for file in files {
+            let Ok(config) = ctx.read_file(&file) else {
+                continue;
+            };
+            settings.extend(parse_pwquality(&config));
+        }
+    }
+    settings.extend(
+        line.split_whitespace()
+            .filter_map(|argument| argument.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string())),
+    );
+
+    // pwquality's defaults, which pam_cracklib shares
+    let mut min_length = 8;
+    let mut complexity = Vec::new();
+    for (key, value) in &settings {
+        match key.as_str() {
+            "minlen" => min_length = value.parse().unwrap_or(min_length),
+            "minclass" | "dcredit" | "ucredit" | "lcredit" | "ocredit" => {
+                complexity.retain(|setting: &String| !setting.starts_with(&format!("{}=", key)));
+                complexity.push(format!("{}={}", key, value));
+            }
+            _ => {}
+        }
+    }
+
+    Some(PasswordQuality {
+        min_length,
+        complexity,
+        source,
+        lines: vec![line],
+    })
+}
+
+/// `key = value` pairs of pwquality.conf, in order.
+#[cfg(any(target_os = "linux", test))]
+fn parse_pwquality(config: &str) -> Vec<(String, String)> {
+    config
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    fn context(root: &str) -> CheckContext {
+        CheckContext::new(FixtureRunner::new()).with_root(fixture_root(root))
+    }
+
+    #[test]
+    fn hardened_accounts_pass() {
+        let outcome = check_linux(&context("linux/accounts_hardened")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.message,
+            "Local accounts require a password of at least 14 characters"
+        );
+
+        let status: AccountStatus = serde_json::from_value(*outcome.details.unwrap()).unwrap();
+        assert_eq!(status.complexity, ["minclass=3", "dcredit=-1"]);
+        assert_eq!(status.max_days, Some(90));
+    }
+
+    #[test]
+    fn weak_accounts_fail() {
+        let outcome = check_linux(&context("linux/accounts_weak")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "Accounts other than root have UID 0: toor; accounts have an empty password: kiosk; the display manager logs in without a password: /etc/gdm3/custom.conf: AutomaticLoginEnable=True"
+        );
+        assert_eq!(outcome.value, None);
+        assert_eq!(outcome.evidence[0].lines, ["toor:x:0:0::/root:/bin/bash"]);
+    }
+
+    #[test]
+    fn missing_passwd_is_an_error() {
+        let outcome = check_linux(&context("linux/empty")).unwrap_err();
+
+        assert_eq!(outcome.status, CheckStatus::Error);
+    }
+}
@@ -0,0 +1,672 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::context::comm_name;
+#[cfg(any(target_os = "linux", test))]
+use chrono::{DateTime, NaiveDateTime};
+#[cfg(any(target_os = "linux", test))]
+use serde::{Deserialize, Serialize};
+
+pub struct AntivirusCheck;
+
+#[async_trait]
+impl SecurityCheck for AntivirusCheck {
+    fn id(&self) -> &'static str {
+        "antivirus"
+    }
+
+    fn title(&self) -> &'static str {
+        "Antivirus Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying antivirus is installed and running"
+    }
+
+    fn category(&self) -> Category {
+        Category::Malware
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        Platform::ALL
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "windows")]
+        let outcome = check_windows(ctx);
+        #[cfg(target_os = "macos")]
+        let outcome = check_macos(ctx);
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx, chrono::Utc::now().naive_utc());
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+fn detected(names: Vec<String>) -> CheckOutcome {
+    let names = names.join(", ");
+    CheckOutcome::pass(format!("Antivirus is installed and running: {}", names)).with_value(names)
+}
+
+#[cfg(any(target_os = "windows", test))]
+fn check_windows(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let output = ctx
+        .run(
+            "wmic",
+            &[
+                "/node:localhost",
+                "/namespace:\\\\root\\SecurityCenter2",
+                "path",
+                "AntiVirusProduct",
+                "Get",
+                "DisplayName",
+            ],
+        )?
+        .ensure_success()?;
+
+    let antivirus_names: Vec<String> = output
+        .stdout
+        .lines()
+        .filter(|s| !s.trim().eq_ignore_ascii_case("DisplayName"))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    if antivirus_names.is_empty() {
+        Ok(
+            CheckOutcome::fail("No antivirus product is registered with Windows Security Center")
+                .with_evidence(output.full_evidence())
+                .with_remediation(
+                    "Turn on Microsoft Defender Antivirus or install an antivirus product",
+                ),
+        )
+    } else {
+        let evidence = output.evidence(&antivirus_names);
+        Ok(detected(antivirus_names).with_evidence(evidence))
+    }
+}
+
+#[cfg(any(target_os = "macos", test))]
+fn check_macos(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    // Check for XProtect/MRT on macOS
+    let queries = [
+        "SELECT * FROM xprotect_entries;",
+        "SELECT * FROM xprotect_meta;",
+        "SELECT * FROM launchd WHERE name LIKE '%com.apple.MRT%' OR name LIKE '%com.apple.XProtect%';",
+        "SELECT * FROM processes WHERE name LIKE '%MRT%' OR name LIKE '%XProtect%';",
+    ];
+
+    let mut evidence = Vec::new();
+    for query in queries.iter() {
+        let output = ctx.run(
+            "sqlite3",
+            &[
+                "/var/db/SystemPolicyConfiguration/XProtect.bundle/Contents/Resources/XProtect.meta.plist",
+                query,
+            ],
+        )?;
+
+        if !output.stdout.trim().is_empty() {
+            return Ok(
+                detected(vec!["XProtect/MRT (Built-in macOS protection)".to_string()])
+                    .with_evidence(output.full_evidence()),
+            );
+        }
+        evidence.push(output.stderr_evidence());
+    }
+
+    Ok(CheckOutcome {
+        evidence,
+        ..CheckOutcome::fail("XProtect/MRT could not be found").with_remediation(
+            "Enable automatic installation of system data files and security updates",
+        )
+    })
+}
+
+/// A product from the embedded `antivirus.toml` catalog.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize)]
+struct Product {
+    vendor: String,
+    product: String,
+    #[serde(default)]
+    units: Vec<String>,
+    #[serde(default)]
+    processes: Vec<String>,
+    #[serde(default)]
+    binaries: Vec<String>,
+    version_command: Option<Vec<String>>,
+    /// Whether the product keeps its signatures in freshclam databases.
+    #[serde(default)]
+    freshclam: bool,
+    realtime_processes: Option<Vec<String>>,
+    realtime_command: Option<Vec<String>>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize)]
+struct Catalog {
+    products: Vec<Product>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn catalog() -> Vec<Product> {
+    toml::from_str::<Catalog>(include_str!("antivirus.toml"))
+        .expect("valid antivirus catalog")
+        .products
+}
+
+/// An antivirus product found on the device.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DetectedProduct {
+    pub vendor: String,
+    pub product: String,
+    pub version: Option<String>,
+    pub running: bool,
+    pub realtime_protection: bool,
+    pub signatures: Option<Signatures>,
+}
+
+/// The newest signature database of a product.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Signatures {
+    pub version: u32,
+    pub built: String,
+    pub age_days: i64,
+}
+
+#[cfg(any(target_os = "linux", test))]
+impl DetectedProduct {
+    fn name(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{} {}", self.product, version),
+            None => self.product.clone(),
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext, now: NaiveDateTime) -> Result<CheckOutcome, CheckOutcome> {
+    let output = ctx
+        .run(
+            "systemctl",
+            &["list-units", "--type=service", "--state=running"],
+        )?
+        .ensure_success()?;
+
+    let processes = ctx.processes();
+    let process_running =
+        |name: &String| processes.iter().any(|process| process == comm_name(name));
+
+    let mut products = Vec::new();
+    let mut signature_products = Vec::new();
+    let mut matched = Vec::new();
+    for product in catalog() {
+        let unit_lines: Vec<String> = output
+            .stdout
+            .lines()
+            .filter(|line| {
+                // UNIT column, after the "●" systemd puts in front of failed units
+                let unit = line
+                    .trim_start_matches([' ', '●'])
+                    .split_whitespace()
+                    .next();
+                unit.is_some_and(|unit| product.units.iter().any(|name| unit_matches(name, unit)))
+            })
+            .map(|line| line.trim().to_string())
+            .collect();
+        let running = !unit_lines.is_empty() || product.processes.iter().any(process_running);
+        let installed = running
+            || product
+                .binaries
+                .iter()
+                .any(|binary| ctx.path(binary).exists());
+        if !installed {
+            continue;
+        }
+
+        let realtime_protection = running
+            && match (&product.realtime_command, &product.realtime_processes) {
+                (Some(command), _) => {
+                    command_output(ctx, command).is_some_and(|output| output.trim() == "true")
+                }
+                (None, Some(names)) => names.iter().any(process_running),
+                (None, None) => true,
+            };
+        let version = product
+            .version_command
+            .as_ref()
+            .and_then(|command| command_output(ctx, command))
+            .and_then(|output| parse_version(&output));
+        let signatures = product
+            .freshclam
+            .then(|| freshclam_signatures(ctx, now))
+            .flatten();
+
+        if product.freshclam {
+            signature_products.push(product.product.clone());
+        }
+        matched.extend(unit_lines);
+        products.push(DetectedProduct {
+            vendor: product.vendor,
+            product: product.product,
+            version,
+            running,
+            realtime_protection,
+            signatures,
+        });
+    }
+
+    let evidence = output.evidence(&matched);
+    let running: Vec<&DetectedProduct> =
+        products.iter().filter(|product| product.running).collect();
+    let outcome = if running.is_empty() {
+        let installed: Vec<String> = products.iter().map(DetectedProduct::name).collect();
+        if installed.is_empty() {
+            CheckOutcome::fail("No running antivirus service was found")
+                .with_remediation("Install and enable an antivirus service such as ClamAV")
+        } else {
+            CheckOutcome::fail(format!(
+                "Antivirus is installed but not running: {}",
+                installed.join(", ")
+            ))
+            .with_remediation("Enable and start the antivirus service")
+        }
+    } else {
+        let names: Vec<String> = running.iter().map(|product| product.name()).collect();
+        let mut warnings = Vec::new();
+        let mut remediations = Vec::new();
+        if !running.iter().any(|product| product.realtime_protection) {
+            warnings.push(format!(
+                "Antivirus is running without real-time protection: {}",
+                names.join(", ")
+            ));
+            remediations.push("Turn on real-time (on-access) scanning, e.g. clamonacc for ClamAV");
+        }
+        let max_age = i64::from(ctx.policy().clamav_max_signature_age_days);
+        for product in &running {
+            let message = match &product.signatures {
+                Some(signatures) if signatures.age_days > max_age => format!(
+                    "{} signatures are {} days old",
+                    product.product, signatures.age_days
+                ),
+                // Without a readable database nothing is up to date
+                None if signature_products.contains(&product.product) => {
+                    format!("{} signature database not found", product.product)
+                }
+                _ => continue,
+            };
+            warnings.push(message);
+            remediations.push("Run `freshclam` and enable the clamav-freshclam service");
+        }
+
+        if warnings.is_empty() {
+            detected(names)
+        } else {
+            CheckOutcome::warn(warnings.join("; "))
+                .with_value(names.join(", "))
+                .with_remediation(remediations.join("; "))
+        }
+    };
+
+    Ok(outcome.with_evidence(evidence).with_details(&products))
+}
+
+/// The newest of ClamAV's daily databases. freshclam keeps either a
+/// `daily.cvd` or, after incremental updates, a `daily.cld`; both start with
+/// the same header.
+#[cfg(any(target_os = "linux", test))]
+fn freshclam_signatures(ctx: &CheckContext, now: NaiveDateTime) -> Option<Signatures> {
+    let directory = ["/etc/clamav/freshclam.conf", "/etc/freshclam.conf"]
+        .iter()
+        .filter_map(|path| ctx.read_file(path).ok())
+        .find_map(|config| {
+            config.lines().find_map(|line| {
+                let directory = line.trim().strip_prefix("DatabaseDirectory")?;
+                Some(directory.trim().to_string())
+            })
+        })
+        .unwrap_or_else(|| "/var/lib/clamav".to_string());
+
+    ["daily.cld", "daily.cvd"]
+        .iter()
+        .filter_map(|name| {
+            let mut header = [0; 512];
+            let mut file =
+                std::fs::File::open(ctx.path(&format!("{}/{}", directory, name))).ok()?;
+            let read = std::io::Read::read(&mut file, &mut header).ok()?;
+            parse_cvd_header(&String::from_utf8_lossy(&header[..read]))
+        })
+        .max_by_key(|(version, _)| *version)
+        .map(|(version, built)| Signatures {
+            version,
+            built: built.format("%Y-%m-%d %H:%M").to_string(),
+            age_days: (now - built).num_days(),
+        })
+}
+
+/// The version and build time (UTC) from a ClamAV database header.
+#[cfg(any(target_os = "linux", test))]
+fn parse_cvd_header(header: &str) -> Option<(u32, NaiveDateTime)> {
+    // ClamAV-VDB:20 Nov 2023 09-33 +0000:27100:2048157:90:<md5>:<dsig>:raynman:1700472831
+    let fields: Vec<&str> = header.trim_end_matches([' ', '\0']).split(':').collect();
+    if fields.first() != Some(&"ClamAV-VDB") {
+        return None;
+    }
+
+    let version = fields.get(2)?.parse().ok()?;
+    let built = fields
+        .get(8)
+        .and_then(|time| time.trim().parse().ok())
+        .and_then(|time| DateTime::from_timestamp(time, 0))
+        .or_else(|| {
+            DateTime::parse_from_str(fields.get(1)?, "%d %b %Y %H-%M %z")
+                .ok()
+                .map(|built| built.to_utc())
+        })?;
+    Some((version, built.naive_utc()))
+}
+
+/// Whether a unit from the catalog names a running unit. Names ending in
+/// `@` match every instance of the template.
+#[cfg(any(target_os = "linux", test))]
+fn unit_matches(name: &str, unit: &str) -> bool {
+    match name.strip_suffix('@') {
+        Some(template) => unit.starts_with(&format!("{}@", template)),
+        None => unit == name,
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn command_output(ctx: &CheckContext, command: &[String]) -> Option<String> {
+    let (program, args) = command.split_first()?;
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = ctx.run(program, &args).ok()?;
+    output.success().then_some(output.stdout)
+}
+
+#[cfg(any(target_os = "linux", test))]
+pub(super) fn parse_version(output: &str) -> Option<String> {
+    regex::Regex::new(r"\d+(?:\.\d+)+")
+        .expect("valid regex")
+        .find(output)
+        .map(|version| version.as_str().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, Policy, fixture_root};
+    use std::io::ErrorKind;
+
+    const SYSTEMCTL: &str = "systemctl list-units --type=service --state=running";
+    const WMIC: &str = r"wmic /node:localhost /namespace:\\root\SecurityCenter2 path AntiVirusProduct Get DisplayName";
+
+    fn now() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2023-11-23 12:00", "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn products(outcome: &CheckOutcome) -> Vec<DetectedProduct> {
+        serde_json::from_value(*outcome.details.clone().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn catalog_products_can_be_detected() {
+        for product in catalog() {
+            assert!(
+                !product.units.is_empty() || !product.processes.is_empty(),
+                "{} has nothing to match",
+                product.product
+            );
+        }
+    }
+
+    #[test]
+    fn linux_detects_running_clamav() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(
+                    SYSTEMCTL,
+                    include_str!("fixtures/linux/systemctl_list_units_clamav.txt"),
+                )
+                .with_output(
+                    "clamd --version",
+                    "ClamAV 1.0.3/27100/Mon Nov 20 09:33:51 2023\n",
+                ),
+        )
+        .with_root(fixture_root("linux/antivirus_clamav"));
+
+        let outcome = check_linux(&ctx, now()).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("ClamAV 1.0.3"));
+        assert_eq!(outcome.evidence[0].lines.len(), 1);
+        assert!(outcome.evidence[0].lines[0].starts_with("clamav-daemon.service"));
+
+        let products = products(&outcome);
+        assert_eq!(products[0].vendor, "Cisco");
+        assert!(products[0].realtime_protection);
+
+        let signatures = products[0].signatures.clone().unwrap();
+        assert_eq!(signatures.version, 27100);
+        assert_eq!(signatures.built, "2023-11-20 09:33");
+        assert_eq!(signatures.age_days, 3);
+    }
+
+    #[test]
+    fn linux_warns_about_outdated_clamav_signatures() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(
+            SYSTEMCTL,
+            include_str!("fixtures/linux/systemctl_list_units_clamav.txt"),
+        ))
+        .with_root(fixture_root("linux/antivirus_clamav"))
+        .with_policy(Policy {
+            clamav_max_signature_age_days: 2,
+            ..Policy::default()
+        });
+
+        let outcome = check_linux(&ctx, now()).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Warn);
+        assert_eq!(outcome.message, "ClamAV signatures are 3 days old");
+        assert_eq!(outcome.value.as_deref(), Some("ClamAV"));
+    }
+
+    #[test]
+    fn parses_cvd_header_without_timestamp_field() {
+        let (version, built) =
+            parse_cvd_header("ClamAV-VDB:09 Feb 2024 08-12 -0500:27180:2051227:90").unwrap();
+
+        assert_eq!(version, 27180);
+        assert_eq!(built.to_string(), "2024-02-09 13:12:00");
+        assert_eq!(parse_cvd_header("PK\u{3}\u{4}"), None);
+    }
+
+    #[test]
+    fn linux_clamav_without_clamonacc_warns() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(
+            SYSTEMCTL,
+            include_str!("fixtures/linux/systemctl_list_units_clamav.txt"),
+        ))
+        .with_root(fixture_root("linux/empty"));
+
+        let outcome = check_linux(&ctx, now()).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Warn);
+        assert!(
+            outcome
+                .message
+                .starts_with("Antivirus is running without real-time protection: ClamAV")
+        );
+    }
+
+    #[test]
+    fn linux_warns_about_missing_clamav_signatures() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(
+            SYSTEMCTL,
+            include_str!("fixtures/linux/systemctl_list_units_clamav.txt"),
+        ))
+        .with_root(fixture_root("linux/antivirus_clamav_no_database"));
+
+        let outcome = check_linux(&ctx, now()).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Warn);
+        assert_eq!(outcome.message, "ClamAV signature database not found");
+        assert_eq!(
+            outcome.remediation.as_deref(),
+            Some("Run `freshclam` and enable the clamav-freshclam service")
+        );
+        assert_eq!(products(&outcome)[0].signatures, None);
+    }
+
+    #[test]
+    fn linux_detects_agents_by_process() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(
+                    SYSTEMCTL,
+                    include_str!("fixtures/linux/systemctl_list_units_none.txt"),
+                )
+                .with_output("mdatp version", "Product version: 101.23082.0006\n")
+                .with_output(
+                    "mdatp health --field real_time_protection_enabled",
+                    "true\n",
+                ),
+        )
+        .with_root(fixture_root("linux/antivirus_mdatp"));
+
+        let outcome = check_linux(&ctx, now()).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.value.as_deref(),
+            Some("Defender for Endpoint 101.23082.0006")
+        );
+    }
+
+    #[test]
+    fn linux_matches_process_names_truncated_by_the_kernel() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(
+            SYSTEMCTL,
+            include_str!("fixtures/linux/systemctl_list_units_none.txt"),
+        ))
+        .with_root(fixture_root("linux/antivirus_sophos"));
+
+        let outcome = check_linux(&ctx, now()).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(products(&outcome)[0].vendor, "Sophos");
+        assert_eq!(comm_name("sophos_threat_detector"), "sophos_threat_d");
+        assert_eq!(comm_name("savd"), "savd");
+    }
+
+    #[test]
+    fn linux_installed_but_stopped_agent_fails() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(
+            SYSTEMCTL,
+            include_str!("fixtures/linux/systemctl_list_units_none.txt"),
+        ))
+        .with_root(fixture_root("linux/antivirus_installed"));
+
+        let outcome = check_linux(&ctx, now()).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "Antivirus is installed but not running: Falcon"
+        );
+        assert!(!products(&outcome)[0].running);
+    }
+
+    #[test]
+    fn template_units_match_their_instances() {
+        assert!(unit_matches("clamd@", "clamd@scan.service"));
+        assert!(!unit_matches("clamd@", "clamd.service"));
+        assert!(!unit_matches("eea.service", "avahi-daemon.service"));
+    }
+
+    #[test]
+    fn linux_without_antivirus_fails_with_remediation() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(
+            SYSTEMCTL,
+            include_str!("fixtures/linux/systemctl_list_units_none.txt"),
+        ))
+        .with_root(fixture_root("linux/empty"));
+
+        let outcome = check_linux(&ctx, now()).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert!(outcome.remediation.is_some());
+    }
+
+    #[test]
+    fn linux_missing_systemctl_is_an_error() {
+        let ctx = CheckContext::new(FixtureRunner::new());
+
+        let outcome = check_linux(&ctx, now()).unwrap_err();
+        assert_eq!(outcome.status, CheckStatus::Error);
+        assert!(outcome.message.contains("not available"));
+    }
+
+    #[test]
+    fn linux_permission_denied_is_an_error() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new().with_error(SYSTEMCTL, ErrorKind::PermissionDenied),
+        );
+
+        let outcome = check_linux(&ctx, now()).unwrap_err();
+        assert_eq!(outcome.status, CheckStatus::Error);
+        assert!(outcome.message.contains("Permission denied"));
+    }
+
+    #[test]
+    fn linux_systemctl_failure_keeps_stderr_as_evidence() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_exit(
+            SYSTEMCTL,
+            1,
+            "",
+            "System has not been booted with systemd as init system (PID 1). Can't operate.\n",
+        ));
+
+        let outcome = check_linux(&ctx, now()).unwrap_err();
+        assert_eq!(outcome.status, CheckStatus::Error);
+        assert_eq!(outcome.evidence[0].exit_code, Some(1));
+        assert!(outcome.evidence[0].lines[0].contains("not been booted with systemd"));
+    }
+
+    #[test]
+    fn windows_lists_security_center_products() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(WMIC, include_str!("fixtures/windows/wmic_antivirus.txt")),
+        );
+
+        let outcome = check_windows(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.value.as_deref(),
+            Some("Windows Defender, ESET Security")
+        );
+    }
+
+    #[test]
+    fn windows_without_products_fails() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(
+            WMIC,
+            include_str!("fixtures/windows/wmic_antivirus_none.txt"),
+        ));
+
+        assert_eq!(check_windows(&ctx).unwrap().status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn macos_detects_xprotect() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(
+            "sqlite3 /var/db/SystemPolicyConfiguration/XProtect.bundle/Contents/Resources/XProtect.meta.plist SELECT * FROM xprotect_entries;",
+            "1|com.apple.XProtect|2192\n",
+        ));
+
+        let outcome = check_macos(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.value.as_deref(),
+            Some("XProtect/MRT (Built-in macOS protection)")
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use serde::{Deserialize, Serialize};
+#[cfg(any(target_os = "linux", test))]
+use std::fmt;
+
+pub struct AutologinCheck;
+
+/// A display manager setting that starts a session without a password.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LoginSetting {
+    pub display_manager: String,
+    pub file: String,
+    pub key: String,
+    pub value: String,
+    /// `autologin` or `guest`.
+    pub kind: String,
+    /// The account logged in automatically, when configured.
+    pub user: Option<String>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+impl fmt::Display for LoginSetting {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}={}", self.file, self.key, self.value)
+    }
+}
+
+#[async_trait]
+impl SecurityCheck for AutologinCheck {
+    fn id(&self) -> &'static str {
+        "automatic_login"
+    }
+
+    fn title(&self) -> &'static str {
+        "Automatic Login Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying the login screen always asks for a password"
+    }
+
+    fn category(&self) -> Category {
+        Category::Access
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Automatic login detection is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let logins = passwordless_logins(ctx);
+    if logins.is_empty() {
+        return Ok(CheckOutcome::pass(
+            "The display manager asks for a password and offers no guest session",
+        )
+        .with_value("disabled")
+        .with_details(&logins));
+    }
+
+    let mut files: Vec<&str> = Vec::new();
+    let mut remediation: Vec<&str> = Vec::new();
+    for login in &logins {
+        if !files.contains(&login.file.as_str()) {
+            files.push(&login.file);
+        }
+        let hint = match login.display_manager.as_str() {
+            "GDM" => "Set AutomaticLoginEnable and TimedLoginEnable to false in GDM's custom.conf.",
+            "LightDM" => {
+                "Remove autologin-user and set allow-guest=false in the LightDM configuration."
+            }
+            _ => "Remove User from the [Autologin] section of the SDDM configuration.",
+        };
+        if !remediation.contains(&hint) {
+            remediation.push(hint);
+        }
+    }
+
+    let outcome = CheckOutcome::fail(format!(
+        "Sessions start without a password: {}",
+        logins
+            .iter()
+            .map(LoginSetting::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+    .with_value("enabled")
+    .with_remediation(remediation.join(" "));
+
+    Ok(files
+        .iter()
+        .map(|file| {
+            let lines: Vec<String> = logins
+                .iter()
+                .filter(|login| login.file == *file)
+                .map(|login| format!("{}={}", login.key, login.value))
+                .collect();
+            Evidence::file(file, &lines)
+        })
+        .fold(outcome, CheckOutcome::with_evidence)
+        .with_details(&logins))
+}
+
+/// Display manager settings that start a session without asking for a
+/// password, after later files have overridden earlier ones.
+#[cfg(any(target_os = "linux", test))]
+pub(super) fn passwordless_logins(ctx: &CheckContext) -> Vec<LoginSetting> {
+    // Drop-ins are read first; the main file has the last word
+    let mut lightdm = drop_ins(ctx, "/etc/lightdm/lightdm.conf.d");
+    lightdm.push("/etc/lightdm/lightdm.conf".to_string());
+    let mut sddm = drop_ins(ctx, "/etc/sddm.conf.d");
+    sddm.push("/etc/sddm.conf".to_string());
+
+    let mut logins = Vec::new();
+    for (display_manager, files) in [
+        ("GDM", vec!["/etc/gdm3/custom.conf".to_string()]),
+        ("GDM", vec!["/etc/gdm/custom.conf".to_string()]),
+        ("LightDM", lightdm),
+        ("SDDM", sddm),
+    ] {
+        let settings = effective_settings(ctx, &files);
+        let get = |section: &str, key: &str| {
+            settings
+                .iter()
+                .find(|setting| setting.section == section && setting.key == key)
+                .map(|setting| setting.value.clone())
+                .filter(|value| !value.is_empty())
+        };
+
+        for setting in &settings {
+            let enabled = setting.value.eq_ignore_ascii_case("true") || setting.value == "1";
+            let seat = setting.section.starts_with("Seat");
+            let (kind, user) = match (display_manager, setting.key.as_str()) {
+                ("GDM", "AutomaticLoginEnable") if setting.section == "daemon" && enabled => {
+                    ("autologin", get("daemon", "AutomaticLogin"))
+                }
+                ("GDM", "TimedLoginEnable") if setting.section == "daemon" && enabled => {
+                    ("autologin", get("daemon", "TimedLogin"))
+                }
+                ("LightDM", "autologin-user") if seat && !setting.value.is_empty() => {
+                    ("autologin", Some(setting.value.clone()))
+                }
+                ("LightDM", "autologin-guest") if seat && enabled => ("autologin", None),
+                ("LightDM", "allow-guest" | "greeter-allow-guest") if seat && enabled => {
+                    ("guest", None)
+                }
+                ("SDDM", "User") if setting.section == "Autologin" && !setting.value.is_empty() => {
+                    ("autologin", Some(setting.value.clone()))
+                }
+                _ => continue,
+            };
+
+            logins.push(LoginSetting {
+                display_manager: display_manager.to_string(),
+                file: setting.file.clone(),
+                key: setting.key.clone(),
+                value: setting.value.clone(),
+                kind: kind.to_string(),
+                user,
+            });
+        }
+    }
+    logins
+}
+
+#[cfg(any(target_os = "linux", test))]
+struct Setting {
+    file: String,
+    section: String,
+    key: String,
+    value: String,
+}
+
+/// The last value of each key across `files`, in the order the keys first
+/// appear.
+#[cfg(any(target_os = "linux", test))]
+fn effective_settings(ctx: &CheckContext, files: &[String]) -> Vec<Setting> {
+    let mut settings: Vec<Setting> = Vec::new();
+    for file in files {
+        let Ok(config) = ctx.read_file(file) else {
+            continue;
+        };
+        for (section, key, value) in ini_entries(&config) {
+            match settings
+                .iter_mut()
+                .find(|setting| setting.section == section && setting.key == key)
+            {
+                Some(setting) => {
+                    setting.file = file.clone();
+                    setting.value = value;
+                }
+                None => settings.push(Setting {
+                    file: file.clone(),
+                    section,
+                    key,
+                    value,
+                }),
+            }
+        }
+    }
+    settings
+}
+
+/// The `.conf` files of a drop-in directory, in the order they are read.
+#[cfg(any(target_os = "linux", test))]
+fn drop_ins(ctx: &CheckContext, dir: &str) -> Vec<String> {
+    let mut files: Vec<String> = std::fs::read_dir(ctx.path(dir))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.ends_with(".conf"))
+        .map(|name| format!("{}/{}", dir, name))
+        .collect();
+    files.sort();
+    files
+}
+
+/// `(section, key, value)` triples of an INI file.
+#[cfg(any(target_os = "linux", test))]
+fn ini_entries(config: &str) -> Vec<(String, String, String)> {
+    let mut section = String::new();
+    let mut entries = Vec::new();
+    for line in config.lines().map(str::trim) {
+        if line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            section = name.to_string();
+        } else if let Some((key, value)) = line.split_once('=') {
+            entries.push((
+                section.clone(),
+                key.trim().to_string(),
+                value.trim().to_string(),
+            ));
+        }
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    fn context(root: &str) -> CheckContext {
+        CheckContext::new(FixtureRunner::new()).with_root(fixture_root(root))
+    }
+
+    #[test]
+    fn gdm_automatic_login_fails() {
+        let outcome = check_linux(&context("linux/accounts_weak")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "Sessions start without a password: /etc/gdm3/custom.conf: AutomaticLoginEnable=True"
+        );
+        assert_eq!(outcome.evidence[0].lines, ["AutomaticLoginEnable=True"]);
+
+        let logins: Vec<LoginSetting> = serde_json::from_value(*outcome.details.unwrap()).unwrap();
+        assert_eq!(logins[0].user.as_deref(), Some("kiosk"));
+    }
+
+    #[test]
+    fn lightdm_main_file_overrides_drop_ins() {
+        let logins = passwordless_logins(&context("linux/autologin_lightdm"));
+
+        assert_eq!(logins.len(), 1);
+        assert_eq!(
+            logins[0].to_string(),
+            "/etc/lightdm/lightdm.conf.d/50-guest.conf: allow-guest=true"
+        );
+        assert_eq!(logins[0].kind, "guest");
+    }
+
+    #[test]
+    fn sddm_drop_in_autologin_fails() {
+        let outcome = check_linux(&context("linux/autologin_sddm")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "Sessions start without a password: /etc/sddm.conf.d/kde_settings.conf: User=alice"
+        );
+    }
+
+    #[test]
+    fn no_display_manager_passes() {
+        let outcome = check_linux(&context("linux/empty")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("disabled"));
+    }
+
+    #[test]
+    fn parses_ini_sections() {
+        let entries = ini_entries("[Seat:*]\n# allow-guest=true\nautologin-user = kiosk\n");
+
+        assert_eq!(
+            entries,
+            [(
+                "Seat:*".to_string(),
+                "autologin-user".to_string(),
+                "kiosk".to_string()
+            )]
+        );
+    }
+}
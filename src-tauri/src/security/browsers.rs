@@ -0,0 +1,536 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use super::antivirus::parse_version;
+
+pub struct BrowsersCheck;
+
+/// A browser installation found on the device.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BrowserInstall {
+    pub name: String,
+    pub version: Option<String>,
+    /// `deb`, `rpm`, `pacman`, `flatpak`, `snap` or `binary`.
+    pub source: String,
+    /// The package, application id, snap or path the browser was found as.
+    pub package: String,
+    /// `Some(false)` when nothing will update the install: a vendor package
+    /// whose repository is not configured, or a held snap. `None` when it
+    /// is left to the package manager or the user.
+    pub auto_update: Option<bool>,
+}
+
+#[async_trait]
+impl SecurityCheck for BrowsersCheck {
+    fn id(&self) -> &'static str {
+        "browsers"
+    }
+
+    fn title(&self) -> &'static str {
+        "Browser Inventory Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Listing installed browsers and whether they receive updates"
+    }
+
+    fn category(&self) -> Category {
+        Category::Updates
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Browser inventory is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+/// A browser from the embedded `browsers.toml` catalog.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize)]
+struct Browser {
+    name: String,
+    #[serde(default)]
+    packages: Vec<String>,
+    flatpak: Option<String>,
+    snap: Option<String>,
+    #[serde(default)]
+    binaries: Vec<String>,
+    repository: Option<String>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize)]
+struct Catalog {
+    browsers: Vec<Browser>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn catalog() -> Vec<Browser> {
+    toml::from_str::<Catalog>(include_str!("browsers.toml"))
+        .expect("valid browser catalog")
+        .browsers
+}
+
+/// A package as its package manager records it.
+#[cfg(any(target_os = "linux", test))]
+struct Package {
+    source: &'static str,
+    name: String,
+    version: String,
+    /// Whether updates are held back, for snaps.
+    held: bool,
+}
+
+#[cfg(any(target_os = "linux", test))]
+const DPKG_STATUS: &str = "/var/lib/dpkg/status";
+#[cfg(any(target_os = "linux", test))]
+const PACMAN_LOCAL: &str = "/var/lib/pacman/local";
+#[cfg(any(target_os = "linux", test))]
+const REPOSITORY_DIRS: &[&str] = &[
+    "/etc/apt/sources.list.d",
+    "/etc/yum.repos.d",
+    "/etc/zypp/repos.d",
+];
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let catalog = catalog();
+    let names: Vec<&str> = catalog
+        .iter()
+        .flat_map(|browser| browser.packages.iter().map(String::as_str))
+        .collect();
+
+    let mut evidence = Vec::new();
+    let mut native = dpkg_packages(ctx, &names);
+    native.extend(pacman_packages(ctx, &names));
+    for (source, file) in [("deb", DPKG_STATUS), ("pacman", PACMAN_LOCAL)] {
+        let lines = package_lines(native.iter().filter(|package| package.source == source));
+        if !lines.is_empty() {
+            evidence.push(Evidence::file(file, &lines));
+        }
+    }
+    let mut args = vec!["-q", "--qf", "%{NAME} %{VERSION}\\n"];
+    args.extend(&names);
+    // rpm exits non-zero when any of the packages is not installed
+    if let Ok(output) = ctx.run("rpm", &args) {
+        let packages = listed_packages(&output.stdout, "rpm", &names);
+        if !packages.is_empty() {
+            evidence.push(output.evidence(&package_lines(&packages)));
+        }
+        native.extend(packages);
+    }
+
+    let flatpaks = sandboxed_packages(
+        ctx,
+        "flatpak",
+        &["list", "--app", "--columns=application,version"],
+        &mut evidence,
+    );
+    let snaps = sandboxed_packages(ctx, "snap", &["list"], &mut evidence);
+
+    let mut installs = Vec::new();
+    for browser in &catalog {
+        let mut found: Vec<&Package> = native
+            .iter()
+            .filter(|package| browser.packages.contains(&package.name))
+            .collect();
+        found.extend(
+            flatpaks
+                .iter()
+                .filter(|package| browser.flatpak.as_ref() == Some(&package.name)),
+        );
+        found.extend(
+            snaps
+                .iter()
+                .filter(|package| browser.snap.as_ref() == Some(&package.name)),
+        );
+
+        for package in found {
+            let auto_update = match (package.source, &browser.repository) {
+                ("snap", _) => Some(!package.held),
+                ("deb" | "rpm", Some(repository)) => Some(repository_configured(ctx, repository)),
+                _ => None,
+            };
+            installs.push(BrowserInstall {
+                name: browser.name.clone(),
+                version: parse_version(&package.version).or_else(|| {
+                    Some(package.version.clone()).filter(|version| !version.is_empty())
+                }),
+                source: package.source.to_string(),
+                package: package.name.clone(),
+                auto_update,
+            });
+        }
+
+        // Builds unpacked by hand, when no package owns the browser
+        if native
+            .iter()
+            .any(|package| browser.packages.contains(&package.name))
+        {
+            continue;
+        }
+        for binary in browser
+            .binaries
+            .iter()
+            .filter(|binary| ctx.path(binary).exists())
+        {
+            let version = ctx
+                .run(binary, &["--version"])
+                .ok()
+                .filter(|output| output.success())
+                .and_then(|output| {
+                    evidence.push(output.evidence(&[output.stdout.trim().to_string()]));
+                    parse_version(&output.stdout)
+                });
+            installs.push(BrowserInstall {
+                name: browser.name.clone(),
+                version,
+                source: "binary".to_string(),
+                package: binary.clone(),
+                auto_update: None,
+            });
+        }
+    }
+
+    let listed: Vec<String> = installs.iter().map(describe).collect();
+    let stale: Vec<&BrowserInstall> = installs
+        .iter()
+        .filter(|install| install.auto_update == Some(false))
+        .collect();
+    let outcome = if installs.is_empty() {
+        CheckOutcome::pass("No supported browsers were found")
+    } else if stale.is_empty() {
+        CheckOutcome::pass(format!("Installed browsers: {}", listed.join(", ")))
+            .with_value(listed.join(", "))
+    } else {
+        let mut remediations = Vec::new();
+        if stale.iter().any(|install| install.source != "snap") {
+            remediations.push(
+                "Reinstall the browser from the vendor's package, which adds its update repository",
+            );
+        }
+        if stale.iter().any(|install| install.source == "snap") {
+            remediations.push("Release the hold with `snap refresh --unhold <snap>`");
+        }
+        CheckOutcome::warn(format!(
+            "Browsers do not update automatically: {}",
+            stale
+                .iter()
+                .map(|install| describe(install))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+        .with_value(listed.join(", "))
+        .with_remediation(remediations.join("; "))
+    };
+
+    Ok(evidence
+        .into_iter()
+        .fold(outcome, CheckOutcome::with_evidence)
+        .with_details(&installs))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn describe(install: &BrowserInstall) -> String {
+    match &install.version {
+        Some(version) => format!("{} {} ({})", install.name, version, install.source),
+        None => format!("{} ({})", install.name, install.source),
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn package_lines<'a>(packages: impl IntoIterator<Item = &'a Package>) -> Vec<String> {
+    packages
+        .into_iter()
+        .map(|package| format!("{} {}", package.name, package.version))
+        .collect()
+}
+
+/// Installed packages from the dpkg database. Transitional packages that
+/// only pull in the snap are skipped, the snap is reported instead.
+#[cfg(any(target_os = "linux", test))]
+fn dpkg_packages(ctx: &CheckContext, names: &[&str]) -> Vec<Package> {
+    let Ok(status) = ctx.read_file(DPKG_STATUS) else {
+        return Vec::new();
+    };
+
+    status
+        .split("\n\n")
+        .filter_map(|stanza| {
+            let field = |name: &str| {
+                stanza.lines().find_map(|line| {
+                    let (key, value) = line.split_once(':')?;
+                    (key == name).then(|| value.trim())
+                })
+            };
+            let name = field("Package")?;
+            let installed = field("Status").is_some_and(|status| status.ends_with(" installed"));
+            let transitional = field("Description")
+                .is_some_and(|description| description.starts_with("Transitional"));
+            (names.contains(&name) && installed && !transitional).then(|| Package {
+                source: "deb",
+                name: name.to_string(),
+                version: field("Version").unwrap_or_default().to_string(),
+                held: false,
+            })
+        })
+        .collect()
+}
+
+/// Installed packages from the pacman database, one directory per package
+/// with a `desc` file.
+#[cfg(any(target_os = "linux", test))]
+fn pacman_packages(ctx: &CheckContext, names: &[&str]) -> Vec<Package> {
+    let mut packages: Vec<Package> = std::fs::read_dir(ctx.path(PACMAN_LOCAL))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let desc = std::fs::read_to_string(entry.path().join("desc")).ok()?;
+            let field = |name: &str| {
+                let mut lines = desc.lines();
+                lines.find(|line| *line == name)?;
+                lines.next().map(str::to_string)
+            };
+            let name = field("%NAME%")?;
+            names.contains(&name.as_str()).then(|| Package {
+                source: "pacman",
+                name,
+                version: field("%VERSION%").unwrap_or_default(),
+                held: false,
+            })
+        })
+        .collect();
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    packages
+}
+
+/// `name version` lines printed by a package manager, keeping the
+/// requested packages.
+#[cfg(any(target_os = "linux", test))]
+fn listed_packages(output: &str, source: &'static str, names: &[&str]) -> Vec<Package> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, version) = line.trim().split_once(' ')?;
+            names.contains(&name).then(|| Package {
+                source,
+                name: name.to_string(),
+                version: version.trim().to_string(),
+                held: false,
+            })
+        })
+        .collect()
+}
+
+/// Applications installed through Flatpak or Snap, with the lines listing
+/// them added to the evidence.
+#[cfg(any(target_os = "linux", test))]
+fn sandboxed_packages(
+    ctx: &CheckContext,
+    program: &'static str,
+    args: &[&str],
+    evidence: &mut Vec<Evidence>,
+) -> Vec<Package> {
+    let Some(output) = ctx
+        .run(program, args)
+        .ok()
+        .filter(|output| output.success())
+    else {
+        return Vec::new();
+    };
+
+    let mut lines = Vec::new();
+    let packages = output
+        .stdout
+        .lines()
+        .filter_map(|line| {
+            // flatpak separates its columns with tabs, snap aligns them with
+            // spaces and starts with a header
+            let mut columns = match program {
+                "flatpak" => line.split('\t').map(str::trim).collect::<Vec<_>>(),
+                _ => line.split_whitespace().collect(),
+            }
+            .into_iter();
+            let name = columns.next().filter(|name| !name.is_empty())?;
+            let version = columns.next().unwrap_or_default();
+            // snap list: Name Version Rev Tracking Publisher Notes
+            let notes = columns.nth(3).unwrap_or_default();
+            if (program == "snap" && name == "Name") || notes.contains("disabled") {
+                return None;
+            }
+            lines.push(line.trim().to_string());
+            Some(Package {
+                source: program,
+                name: name.to_string(),
+                version: version.to_string(),
+                held: notes.contains("held"),
+            })
+        })
+        .collect();
+
+    if !lines.is_empty() {
+        evidence.push(output.evidence(&lines));
+    }
+    packages
+}
+
+/// Whether an apt, yum or zypper repository file for a vendor is present
+/// and enabled. Release upgrades comment out third-party apt sources and
+/// yum repositories can be turned off with `enabled=0`.
+#[cfg(any(target_os = "linux", test))]
+fn repository_configured(ctx: &CheckContext, repository: &str) -> bool {
+    REPOSITORY_DIRS.iter().any(|dir| {
+        std::fs::read_dir(ctx.path(dir))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.contains(repository)
+                    && [".list", ".sources", ".repo"]
+                        .iter()
+                        .any(|extension| name.ends_with(extension))
+            })
+            .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+            .any(|contents| {
+                let lines: Vec<String> = contents
+                    .lines()
+                    .map(|line| line.replace(' ', ""))
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .collect();
+                !lines.is_empty()
+                    && !lines
+                        .iter()
+                        .any(|line| line == "enabled=0" || line.eq_ignore_ascii_case("Enabled:no"))
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    const FLATPAK: &str = "flatpak list --app --columns=application,version";
+
+    fn installs(outcome: &CheckOutcome) -> Vec<BrowserInstall> {
+        serde_json::from_value(*outcome.details.clone().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn catalog_browsers_can_be_found() {
+        for browser in catalog() {
+            assert!(
+                !browser.packages.is_empty() || browser.flatpak.is_some() || browser.snap.is_some(),
+                "{} has nothing to match",
+                browser.name
+            );
+        }
+    }
+
+    #[test]
+    fn lists_debian_packages_flatpaks_and_snaps() {
+        let runner = FixtureRunner::new()
+            .with_output(
+                FLATPAK,
+                "org.chromium.Chromium\t129.0.6668.100\norg.gnome.Calculator\t46.1\n",
+            )
+            .with_output("snap list", include_str!("fixtures/linux/snap_list.txt"));
+        let ctx = CheckContext::new(runner).with_root(fixture_root("linux/browsers_deb"));
+
+        let outcome = check_linux(&ctx).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.message,
+            "Installed browsers: Firefox 131.0.3 (snap), Google Chrome 129.0.6668.100 (deb), Chromium 129.0.6668.100 (flatpak)"
+        );
+        assert_eq!(
+            installs(&outcome)[1],
+            BrowserInstall {
+                name: "Google Chrome".to_string(),
+                version: Some("129.0.6668.100".to_string()),
+                source: "deb".to_string(),
+                package: "google-chrome-stable".to_string(),
+                auto_update: Some(true),
+            }
+        );
+    }
+
+    #[test]
+    fn vendor_package_without_repository_warns() {
+        let ctx = CheckContext::new(FixtureRunner::new())
+            .with_root(fixture_root("linux/browsers_no_repository"));
+
+        let outcome = check_linux(&ctx).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Warn);
+        assert_eq!(
+            outcome.message,
+            "Browsers do not update automatically: Microsoft Edge 129.0.2792.89 (deb)"
+        );
+    }
+
+    #[test]
+    fn finds_pacman_packages_and_unpacked_builds() {
+        let runner = FixtureRunner::new().with_output(
+            "/opt/firefox/firefox --version",
+            "Mozilla Firefox 130.0.1\n",
+        );
+        let ctx = CheckContext::new(runner).with_root(fixture_root("linux/browsers_pacman"));
+
+        let outcome = check_linux(&ctx).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.value.as_deref(),
+            Some("Firefox 130.0.1 (binary), Brave 1.70.126 (pacman)")
+        );
+    }
+
+    #[test]
+    fn held_snap_warns() {
+        let runner = FixtureRunner::new().with_output(
+            "snap list",
+            "Name      Version    Rev   Tracking       Publisher   Notes\nbrave     1.70.123   454   latest/stable  brave       held\n",
+        );
+        let ctx = CheckContext::new(runner).with_root(fixture_root("linux/empty"));
+
+        let outcome = check_linux(&ctx).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Warn);
+        assert_eq!(
+            outcome.remediation.as_deref(),
+            Some("Release the hold with `snap refresh --unhold <snap>`")
+        );
+    }
+
+    #[test]
+    fn no_browsers_passes() {
+        let ctx = CheckContext::new(FixtureRunner::new()).with_root(fixture_root("linux/empty"));
+
+        let outcome = check_linux(&ctx).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert!(installs(&outcome).is_empty());
+    }
+}
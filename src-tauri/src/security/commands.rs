@@ -1,22 +1,40 @@
-use crate::security::{check_antivirus, check_disk_encryption, check_screen_lock};
-use serde::Serialize;
+use super::{CheckContext, CheckInfo, CheckOutcome, Policy, PolicyStore, Registry};
+use tauri::State;
 
-#[derive(Serialize, Debug)]
-pub struct SecurityInfo {
-    antivirus: Option<String>,
-    disk_encryption: Option<String>,
-    screen_lock: Option<u32>,
+#[tauri::command]
+pub async fn list_checks(registry: State<'_, Registry>) -> Result<Vec<CheckInfo>, String> {
+    Ok(registry.iter().map(|check| check.info()).collect())
 }
 
 #[tauri::command]
-pub async fn get_security_info() -> Result<SecurityInfo, String> {
-    let antivirus = check_antivirus().await;
-    let disk_encryption = check_disk_encryption().await;
-    let screen_lock = check_screen_lock().await;
+pub async fn run_check(
+    registry: State<'_, Registry>,
+    policy: State<'_, PolicyStore>,
+    id: String,
+) -> Result<CheckOutcome, String> {
+    let ctx = CheckContext::default().with_policy(policy.policy());
+    registry
+        .run(&id, &ctx)
+        .await
+        .ok_or_else(|| format!("Unknown security check: {}", id))
+}
 
-    Ok(SecurityInfo {
-        antivirus,
-        disk_encryption,
-        screen_lock,
-    })
-} 
\ No newline at end of file
+#[tauri::command]
+pub async fn get_policy(policy: State<'_, PolicyStore>) -> Result<Policy, String> {
+    Ok(policy.policy())
+}
+
+#[tauri::command]
+pub async fn set_policy(
+    policy_store: State<'_, PolicyStore>,
+    policy: Policy,
+) -> Result<Policy, String> {
+    if policy.clamav_max_signature_age_days == 0 {
+        return Err("The ClamAV signature age must be at least one day".to_string());
+    }
+    if policy.max_update_age_days == 0 {
+        return Err("The update age must be at least one day".to_string());
+    }
+    policy_store.set(policy.clone())?;
+    Ok(policy)
+}
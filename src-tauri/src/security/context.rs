@@ -0,0 +1,123 @@
+use super::runner::{CommandOutput, CommandRunner, SystemRunner};
+use super::{CheckOutcome, Policy};
+use std::collections::HashMap;
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Everything a check needs to inspect the host.
+#[derive(Clone)]
+pub struct CheckContext {
+    runner: Arc<dyn CommandRunner>,
+    root: PathBuf,
+    env: Option<HashMap<String, String>>,
+    policy: Policy,
+}
+
+impl CheckContext {
+    pub fn new(runner: impl CommandRunner + 'static) -> Self {
+        CheckContext {
+            runner: Arc::new(runner),
+            root: PathBuf::from("/"),
+            env: None,
+            policy: Policy::default(),
+        }
+    }
+
+    /// Resolves absolute paths read by the checks against `root` instead of
+    /// `/`, so tests can point them at a fixture directory.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = root.into();
+        self
+    }
+
+    /// Replaces the process environment with an explicit set of variables,
+    /// starting from an empty one.
+    pub fn with_env(mut self, name: &str, value: &str) -> Self {
+        self.env
+            .get_or_insert_with(HashMap::new)
+            .insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_policy(mut self, policy: Policy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    /// A non-empty environment variable of the session the checks run in.
+    pub fn env(&self, name: &str) -> Option<String> {
+        match &self.env {
+            Some(env) => env.get(name).cloned(),
+            None => std::env::var(name).ok(),
+        }
+        .filter(|value| !value.is_empty())
+    }
+
+    pub fn path(&self, path: &str) -> PathBuf {
+        self.root.join(path.trim_start_matches('/'))
+    }
+
+    pub fn read_file(&self, path: &str) -> io::Result<String> {
+        std::fs::read_to_string(self.path(path))
+    }
+
+    /// Names of the running processes, from `/proc/<pid>/comm`. The kernel
+    /// truncates these, so compare them with `comm_name`.
+    pub fn processes(&self) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(self.path("/proc")) else {
+            return Vec::new();
+        };
+
+        entries
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().parse::<u32>().is_ok())
+            .filter_map(|entry| std::fs::read_to_string(entry.path().join("comm")).ok())
+            .map(|comm| comm.trim().to_string())
+            .collect()
+    }
+
+    /// Runs a command, turning the reasons it could not be run at all
+    /// (missing binary, permission denied) into an error outcome.
+    pub fn run(&self, program: &str, args: &[&str]) -> Result<CommandOutput, CheckOutcome> {
+        self.runner.run(program, args).map_err(|e| match e.kind() {
+            ErrorKind::NotFound => {
+                CheckOutcome::error(format!("`{}` is not available on this system", program))
+            }
+            ErrorKind::PermissionDenied => {
+                CheckOutcome::error(format!("Permission denied running `{}`", program))
+            }
+            _ => CheckOutcome::error(format!("Could not run `{}`: {}", program, e)),
+        })
+    }
+}
+
+/// How a process name appears in `/proc/<pid>/comm`, which holds at most
+/// 15 bytes.
+#[cfg(any(target_os = "linux", test))]
+pub(super) fn comm_name(name: &str) -> &str {
+    const TASK_COMM_LEN: usize = 15;
+    let mut end = name.len().min(TASK_COMM_LEN);
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    &name[..end]
+}
+
+impl Default for CheckContext {
+    fn default() -> Self {
+        CheckContext::new(SystemRunner)
+    }
+}
+
+/// A directory under `security/fixtures` laid out like a filesystem root.
+#[cfg(test)]
+pub fn fixture_root(name: &str) -> PathBuf {
+    std::path::Path::new(file!())
+        .with_file_name("fixtures")
+        .join(name)
+}
@@ -0,0 +1,668 @@
+#[cfg(any(target_os = "linux", test))]
+use super::runner::CommandOutput;
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(target_os = "windows", target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use std::collections::HashMap;
+
+pub struct DiskEncryptionCheck;
+
+/// A mounted volume and how, if at all, it is encrypted.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Volume {
+    pub mountpoint: String,
+    pub device: String,
+    pub encryption: Option<String>,
+    pub cipher: Option<String>,
+    /// Which of `root`, `home` and `swap` the volume backs.
+    pub roles: Vec<String>,
+    pub removable: bool,
+}
+
+impl Volume {
+    fn new(mountpoint: &str, device: &str) -> Self {
+        Volume {
+            mountpoint: mountpoint.to_string(),
+            device: device.to_string(),
+            encryption: None,
+            cipher: None,
+            roles: Vec::new(),
+            removable: false,
+        }
+    }
+
+    /// Whether the "all fixed volumes encrypted" policy applies. Boot
+    /// partitions cannot be encrypted, and swap has its own check.
+    #[cfg(any(target_os = "windows", target_os = "linux", test))]
+    fn is_fixed(&self) -> bool {
+        !self.removable
+            && !matches!(self.mountpoint.as_str(), "/boot" | "/boot/efi" | "/efi")
+            && !self.roles.iter().any(|role| role == "swap")
+    }
+
+    #[cfg(any(target_os = "linux", test))]
+    fn summary(&self) -> String {
+        format!(
+            "{} on {}: {}",
+            self.mountpoint,
+            self.device,
+            self.encryption.as_deref().unwrap_or("not encrypted")
+        )
+    }
+}
+
+#[async_trait]
+impl SecurityCheck for DiskEncryptionCheck {
+    fn id(&self) -> &'static str {
+        "disk_encryption"
+    }
+
+    fn title(&self) -> &'static str {
+        "Disk Encryption Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying disk encryption is enabled"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encryption
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        Platform::ALL
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "windows")]
+        let outcome = check_windows(ctx);
+        #[cfg(target_os = "macos")]
+        let outcome = check_macos(ctx);
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+fn encrypted(method: &str) -> CheckOutcome {
+    CheckOutcome::pass(format!("Disk encryption is enabled: {}", method)).with_value(method)
+}
+
+/// Applies the "all fixed volumes encrypted" policy to an inventory.
+#[cfg(any(target_os = "windows", target_os = "linux", test))]
+fn evaluate(volumes: Vec<Volume>, evidence: Evidence, remediation: &str) -> CheckOutcome {
+    let fixed: Vec<&Volume> = volumes.iter().filter(|volume| volume.is_fixed()).collect();
+    let unencrypted: Vec<&str> = fixed
+        .iter()
+        .filter(|volume| volume.encryption.is_none())
+        .map(|volume| volume.mountpoint.as_str())
+        .collect();
+
+    let outcome = if fixed.is_empty() {
+        CheckOutcome::error("No fixed volumes were found")
+    } else if unencrypted.is_empty() {
+        let mut methods: Vec<&str> = Vec::new();
+        for method in fixed
+            .iter()
+            .filter_map(|volume| volume.encryption.as_deref())
+        {
+            if !methods.contains(&method) {
+                methods.push(method);
+            }
+        }
+        encrypted(&methods.join(", "))
+    } else {
+        CheckOutcome::fail(format!(
+            "{} of {} fixed volumes are not encrypted: {}",
+            unencrypted.len(),
+            fixed.len(),
+            unencrypted.join(", ")
+        ))
+        .with_remediation(remediation)
+    };
+
+    outcome.with_evidence(evidence).with_details(&volumes)
+}
+
+#[cfg(any(target_os = "windows", test))]
+const BITLOCKER: &str = "Get-CimInstance Win32_LogicalDisk -Filter 'DriveType=3' | ForEach-Object { $_.DeviceID + '=' + (New-Object -ComObject Shell.Application).NameSpace($_.DeviceID).Self.ExtendedProperty('System.Volume.BitLockerProtection') }";
+
+#[cfg(any(target_os = "windows", test))]
+fn check_windows(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let output = ctx
+        .run("powershell", &["-Command", BITLOCKER])?
+        .ensure_success()?;
+
+    let lines: Vec<String> = output
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.contains('='))
+        .map(str::to_string)
+        .collect();
+    let evidence = output.evidence(&lines);
+    let system_drive = ctx.env("SystemDrive").unwrap_or_else(|| "C:".to_string());
+
+    let mut volumes = Vec::new();
+    for line in &lines {
+        let (drive, protection) = line.split_once('=').unwrap_or_default();
+        let mut volume = Volume::new(drive, drive);
+        volume.encryption = match protection {
+            "1" => Some("BitLocker".to_string()),
+            "7" => Some("Bitlocker: only space used".to_string()),
+            value if value.parse::<u32>().is_ok() => None,
+            _ => {
+                return Err(CheckOutcome::error(format!(
+                    "Could not read the BitLocker protection status of drive {}",
+                    drive
+                ))
+                .with_evidence(evidence));
+            }
+        };
+        if drive.eq_ignore_ascii_case(&system_drive) {
+            volume.roles.push("root".to_string());
+        }
+        volumes.push(volume);
+    }
+
+    Ok(evaluate(
+        volumes,
+        evidence,
+        "Turn on BitLocker for every fixed drive",
+    ))
+}
+
+#[cfg(any(target_os = "macos", test))]
+fn check_macos(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let output = ctx.run("diskutil", &["info", "/"])?.ensure_success()?;
+
+    let filevault: Vec<String> = output
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("FileVault:"))
+        .map(str::to_string)
+        .collect();
+    let evidence = output.evidence(&filevault);
+    let device = output
+        .stdout
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Device Node:"))
+        .unwrap_or_default()
+        .trim();
+
+    let mut volume = Volume::new("/", device);
+    volume.roles = vec!["root".to_string(), "home".to_string()];
+
+    match filevault.first() {
+        Some(line) if line.contains("Yes") => {
+            volume.encryption = Some("FileVault".to_string());
+            Ok(encrypted("FileVault")
+                .with_evidence(evidence)
+                .with_details(&[volume]))
+        }
+        Some(_) => Ok(CheckOutcome::fail("FileVault is turned off")
+            .with_evidence(evidence)
+            .with_remediation("Turn on FileVault in System Settings > Privacy & Security")
+            .with_details(&[volume])),
+        None => Err(
+            CheckOutcome::error("`diskutil` did not report the FileVault status")
+                .with_evidence(output.full_evidence()),
+        ),
+    }
+}
+
+/// A node of `lsblk -J` output. Older util-linux versions print flags as
+/// "0"/"1" strings rather than booleans.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize)]
+struct BlockDevice {
+    name: String,
+    kname: Option<String>,
+    path: Option<String>,
+    #[serde(rename = "maj:min")]
+    number: Option<String>,
+    #[serde(rename = "type")]
+    kind: String,
+    fstype: Option<String>,
+    #[serde(default, deserialize_with = "flag")]
+    rm: bool,
+    #[serde(default, deserialize_with = "flag")]
+    hotplug: bool,
+    #[serde(default)]
+    children: Vec<BlockDevice>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn flag<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(value.as_bool() == Some(true) || value.as_str() == Some("1"))
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize)]
+struct Lsblk {
+    blockdevices: Vec<BlockDevice>,
+}
+
+/// What a block device inherits from the devices it sits on.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Clone, Default)]
+pub(super) struct Backing {
+    pub(super) encryption: Option<String>,
+    /// The device-mapper name of the crypt device, as listed by `dmsetup`.
+    pub(super) crypt_name: Option<String>,
+    pub(super) removable: bool,
+    /// The device's own path and `MAJ:MIN` number, which are not inherited.
+    pub(super) path: String,
+    pub(super) number: Option<String>,
+}
+
+/// Flattens the `lsblk` tree into each device path and what backs it, so
+/// an LVM volume inside a LUKS container counts as encrypted. Devices are
+/// listed under both their path and kernel name, since `/proc/swaps` shows
+/// device-mapper devices as `/dev/dm-N`.
+#[cfg(any(target_os = "linux", test))]
+fn parse_lsblk(json: &str) -> Option<HashMap<String, Backing>> {
+    fn walk(
+        device: &BlockDevice,
+        parent_fstype: Option<&str>,
+        inherited: &Backing,
+        devices: &mut HashMap<String, Backing>,
+    ) {
+        let mut backing = inherited.clone();
+        backing.removable |= device.rm || device.hotplug;
+        if device.kind == "crypt" {
+            backing.encryption = Some(match parent_fstype {
+                Some("crypto_LUKS") => "LUKS".to_string(),
+                _ => "dm-crypt".to_string(),
+            });
+            backing.crypt_name = Some(device.name.clone());
+        }
+
+        for child in &device.children {
+            walk(child, device.fstype.as_deref(), &backing, devices);
+        }
+
+        backing.path = device
+            .path
+            .clone()
+            .unwrap_or_else(|| format!("/dev/{}", device.name));
+        backing.number = device.number.clone();
+        if let Some(kname) = &device.kname {
+            devices.insert(format!("/dev/{}", kname), backing.clone());
+        }
+        devices.insert(backing.path.clone(), backing);
+    }
+
+    let lsblk: Lsblk = serde_json::from_str(json).ok()?;
+    let mut devices = HashMap::new();
+    for device in &lsblk.blockdevices {
+        walk(device, None, &Backing::default(), &mut devices);
+    }
+    Some(devices)
+}
+
+/// Ciphers of the active dm-crypt mappings, keyed by mapping name.
+#[cfg(any(target_os = "linux", test))]
+fn parse_dmsetup_ciphers(output: &str) -> HashMap<String, String> {
+    // luks-1d2c...: 0 975765504 crypt aes-xts-plain64 :64:logon:cryptsetup:... 0 259:2 32768
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, table) = line.split_once(':')?;
+            let mut fields = table.split_whitespace().skip(2);
+            (fields.next()? == "crypt").then_some(())?;
+            Some((name.trim().to_string(), fields.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// Every block device and what backs it, along with the `lsblk` output.
+#[cfg(any(target_os = "linux", test))]
+pub(super) fn block_devices(
+    ctx: &CheckContext,
+) -> Result<(HashMap<String, Backing>, CommandOutput), CheckOutcome> {
+    let lsblk = ctx
+        .run(
+            "lsblk",
+            &["-J", "-o", "NAME,KNAME,PATH,MAJ:MIN,TYPE,FSTYPE,RM,HOTPLUG"],
+        )?
+        .ensure_success()?;
+    let devices = parse_lsblk(&lsblk.stdout).ok_or_else(|| {
+        CheckOutcome::error("Could not parse the output of `lsblk`")
+            .with_evidence(lsblk.full_evidence())
+    })?;
+    Ok((devices, lsblk))
+}
+
+#[cfg(any(target_os = "linux", test))]
+pub(super) struct Mount {
+    pub(super) source: String,
+    pub(super) mountpoint: String,
+    pub(super) fstype: String,
+}
+
+#[cfg(any(target_os = "linux", test))]
+pub(super) fn read_mounts(ctx: &CheckContext) -> Result<Vec<Mount>, CheckOutcome> {
+    let mounts = ctx
+        .read_file("/proc/mounts")
+        .map_err(|e| CheckOutcome::error(format!("Could not read /proc/mounts: {}", e)))?;
+
+    Ok(mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(Mount {
+                source: fields.next()?.to_string(),
+                mountpoint: fields.next()?.replace("\\040", " "),
+                fstype: fields.next()?.to_string(),
+            })
+        })
+        .collect())
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let (devices, lsblk) = block_devices(ctx)?;
+
+    // Listing the mapping tables needs root; without it there is no cipher
+    let ciphers = ctx
+        .run("dmsetup", &["table", "--target", "crypt"])
+        .ok()
+        .filter(|output| output.success())
+        .map(|output| parse_dmsetup_ciphers(&output.stdout))
+        .unwrap_or_default();
+
+    let mut volumes = Vec::new();
+    for Mount {
+        source,
+        mountpoint,
+        fstype,
+    } in read_mounts(ctx)?
+    {
+        let source = source.as_str();
+        let mut volume = Volume::new(&mountpoint, source);
+        match fstype.as_str() {
+            "ecryptfs" => volume.encryption = Some("ecryptfs".to_string()),
+            "zfs" => volume.encryption = zfs_encryption(ctx, source),
+            "squashfs" => continue,
+            _ if source.starts_with("/dev/") && !source.starts_with("/dev/loop") => {
+                let backing = devices.get(source).cloned().unwrap_or_default();
+                volume.removable = backing.removable;
+                volume.encryption = backing.encryption;
+                volume.cipher = backing
+                    .crypt_name
+                    .and_then(|name| ciphers.get(&name).cloned());
+                if volume.encryption.is_none() && matches!(fstype.as_str(), "ext4" | "f2fs") {
+                    volume.encryption = fscrypt_encryption(ctx, &mountpoint);
+                }
+            }
+            _ => continue,
+        }
+        volumes.push(volume);
+    }
+
+    if let Ok(swaps) = ctx.read_file("/proc/swaps") {
+        for line in swaps.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [device, "partition", ..] = fields[..] else {
+                continue;
+            };
+            let backing = devices.get(device).cloned().unwrap_or_default();
+            let mut volume = Volume::new("[SWAP]", device);
+            volume.encryption = backing.encryption;
+            volume.removable = backing.removable;
+            volume.roles.push("swap".to_string());
+            volumes.push(volume);
+        }
+    }
+
+    assign_roles(&mut volumes);
+    let summary: Vec<String> = volumes.iter().map(Volume::summary).collect();
+
+    Ok(evaluate(
+        volumes,
+        lsblk.evidence(&summary),
+        "Encrypt the system disk with LUKS, usually at install time",
+    ))
+}
+
+/// Marks the volumes that hold `/` and `/home`, which may be the same one.
+#[cfg(any(target_os = "linux", test))]
+fn assign_roles(volumes: &mut [Volume]) {
+    for (role, path) in [("root", "/"), ("home", "/home")] {
+        let backing = volumes
+            .iter()
+            .enumerate()
+            .filter(|(_, volume)| !volume.roles.iter().any(|role| role == "swap"))
+            .filter(|(_, volume)| {
+                let mountpoint = volume.mountpoint.trim_end_matches('/');
+                path == volume.mountpoint || path.starts_with(&format!("{}/", mountpoint))
+            })
+            .max_by_key(|(_, volume)| volume.mountpoint.len())
+            .map(|(index, _)| index);
+        if let Some(index) = backing {
+            volumes[index].roles.push(role.to_string());
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn zfs_encryption(ctx: &CheckContext, dataset: &str) -> Option<String> {
+    let output = ctx
+        .run("zfs", &["get", "-H", "-o", "value", "encryption", dataset])
+        .ok()?;
+    match output.stdout.trim() {
+        "" | "-" | "off" => None,
+        _ => Some("ZFS".to_string()),
+    }
+}
+
+/// fscrypt encrypts directories rather than the whole filesystem, so it
+/// only counts when at least one policy has been applied.
+#[cfg(any(target_os = "linux", test))]
+fn fscrypt_encryption(ctx: &CheckContext, mountpoint: &str) -> Option<String> {
+    // ext4 filesystem "/home" has 1 protector and 1 policy
+    let output = ctx.run("fscrypt", &["status", mountpoint]).ok()?;
+    let summary = output.stdout.lines().next()?;
+    let policies = summary
+        .split(" and ")
+        .nth(1)?
+        .split_whitespace()
+        .next()?
+        .parse::<u32>()
+        .ok()?;
+    (policies > 0).then(|| "fscrypt".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    const LSBLK: &str = "lsblk -J -o NAME,KNAME,PATH,MAJ:MIN,TYPE,FSTYPE,RM,HOTPLUG";
+
+    fn volumes(outcome: &CheckOutcome) -> Vec<Volume> {
+        serde_json::from_value(*outcome.details.clone().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn linux_luks_with_lvm_passes() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(LSBLK, include_str!("fixtures/linux/lsblk_json_luks.txt"))
+                .with_output(
+                    "dmsetup table --target crypt",
+                    "luks-7c1f0b2e: 0 975765504 crypt aes-xts-plain64 :64:logon:cryptsetup:7c1f0b2e-d0 0 259:2 32768\n",
+                ),
+        )
+        .with_root(fixture_root("linux/disks_luks"));
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("LUKS"));
+
+        let volumes = volumes(&outcome);
+        let root = volumes
+            .iter()
+            .find(|volume| volume.mountpoint == "/")
+            .unwrap();
+        assert_eq!(root.device, "/dev/mapper/vg-root");
+        assert_eq!(root.cipher.as_deref(), Some("aes-xts-plain64"));
+        assert_eq!(root.roles, ["root"]);
+
+        let home = volumes
+            .iter()
+            .find(|volume| volume.roles.contains(&"home".to_string()));
+        assert_eq!(home.unwrap().mountpoint, "/home");
+
+        let usb = volumes
+            .iter()
+            .find(|volume| volume.mountpoint == "/media/alice/USB")
+            .unwrap();
+        assert!(usb.removable);
+        assert_eq!(usb.encryption, None);
+    }
+
+    #[test]
+    fn linux_unencrypted_data_volume_fails() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(LSBLK, include_str!("fixtures/linux/lsblk_json_mixed.txt")),
+        )
+        .with_root(fixture_root("linux/disks_mixed"));
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "1 of 2 fixed volumes are not encrypted: /data"
+        );
+
+        let swap = volumes(&outcome)
+            .into_iter()
+            .find(|volume| volume.mountpoint == "[SWAP]")
+            .unwrap();
+        assert_eq!(swap.roles, ["swap"]);
+        assert_eq!(swap.encryption, None);
+    }
+
+    #[test]
+    fn linux_ecryptfs_home_does_not_cover_root() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(LSBLK, include_str!("fixtures/linux/lsblk_json_mixed.txt")),
+        )
+        .with_root(fixture_root("linux/disks_ecryptfs"));
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(outcome.message, "1 of 2 fixed volumes are not encrypted: /");
+
+        let private = volumes(&outcome)
+            .into_iter()
+            .find(|volume| volume.mountpoint == "/home/alice")
+            .unwrap();
+        assert_eq!(private.encryption.as_deref(), Some("ecryptfs"));
+        assert_eq!(private.roles, Vec::<String>::new());
+    }
+
+    #[test]
+    fn linux_zfs_native_encryption_and_fscrypt() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(LSBLK, include_str!("fixtures/linux/lsblk_json_mixed.txt"))
+                .with_output(
+                    "zfs get -H -o value encryption rpool/ROOT/ubuntu",
+                    "aes-256-gcm\n",
+                )
+                .with_output(
+                    "zfs get -H -o value encryption rpool/USERDATA/alice",
+                    "aes-256-gcm\n",
+                )
+                .with_output(
+                    "fscrypt status /data",
+                    "ext4 filesystem \"/data\" has 1 protector and 2 policies\n",
+                ),
+        )
+        .with_root(fixture_root("linux/disks_zfs"));
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("ZFS, fscrypt"));
+    }
+
+    #[test]
+    fn parses_legacy_lsblk_flags() {
+        let devices = parse_lsblk(
+            r#"{"blockdevices": [{"name": "sdb", "type": "disk", "fstype": null, "rm": "1", "hotplug": "1",
+                "children": [{"name": "sdb1", "type": "part", "fstype": "vfat", "rm": "1", "hotplug": "1"}]}]}"#,
+        )
+        .unwrap();
+
+        assert!(devices["/dev/sdb1"].removable);
+        assert_eq!(devices["/dev/sdb1"].encryption, None);
+    }
+
+    #[test]
+    fn parses_dmsetup_crypt_tables() {
+        let ciphers = parse_dmsetup_ciphers(
+            "luks-7c1f0b2e: 0 975765504 crypt aes-xts-plain64 :64:logon:cryptsetup:7c1f0b2e-d0 0 259:2 32768\nvg-root: 0 104857600 linear 253:0 2048\n",
+        );
+
+        assert_eq!(ciphers.len(), 1);
+        assert_eq!(ciphers["luks-7c1f0b2e"], "aes-xts-plain64");
+    }
+
+    #[test]
+    fn windows_checks_every_fixed_drive() {
+        let command = format!("powershell -Command {}", BITLOCKER);
+        let all = CheckContext::new(FixtureRunner::new().with_output(&command, "C:=1\r\nD:=1\r\n"));
+        let partial =
+            CheckContext::new(FixtureRunner::new().with_output(&command, "C:=1\r\nD:=2\r\n"));
+        let unknown = CheckContext::new(FixtureRunner::new().with_output(&command, "C:=\r\n"));
+
+        assert_eq!(
+            check_windows(&all).unwrap().value.as_deref(),
+            Some("BitLocker")
+        );
+
+        let outcome = check_windows(&partial).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "1 of 2 fixed volumes are not encrypted: D:"
+        );
+        assert_eq!(volumes(&outcome)[0].roles, ["root"]);
+
+        assert_eq!(
+            check_windows(&unknown).unwrap_err().status,
+            CheckStatus::Error
+        );
+    }
+
+    #[test]
+    fn macos_reads_filevault_status() {
+        let on = CheckContext::new(FixtureRunner::new().with_output(
+            "diskutil info /",
+            include_str!("fixtures/macos/diskutil_info_filevault_on.txt"),
+        ));
+        let off = CheckContext::new(FixtureRunner::new().with_output(
+            "diskutil info /",
+            include_str!("fixtures/macos/diskutil_info_filevault_off.txt"),
+        ));
+
+        assert_eq!(
+            check_macos(&on).unwrap().value.as_deref(),
+            Some("FileVault")
+        );
+        assert_eq!(check_macos(&off).unwrap().status, CheckStatus::Fail);
+    }
+}
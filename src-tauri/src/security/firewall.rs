@@ -0,0 +1,730 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+
+pub struct FirewallCheck;
+
+#[async_trait]
+impl SecurityCheck for FirewallCheck {
+    fn id(&self) -> &'static str {
+        "firewall"
+    }
+
+    fn title(&self) -> &'static str {
+        "Firewall Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying a firewall blocks inbound traffic by default"
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Firewall detection is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+struct Firewall {
+    name: &'static str,
+    active: bool,
+    /// `None` when the firewall is known to be active but its rules could
+    /// not be read.
+    default_deny: Option<bool>,
+    evidence: Vec<Evidence>,
+}
+
+/// What probing a single firewall backend found.
+#[cfg(any(target_os = "linux", test))]
+enum Probe {
+    Missing,
+    Found(Firewall),
+    /// Installed, but neither its state nor its configuration could be read,
+    /// usually for lack of root.
+    Unreadable(Evidence),
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    // Front-ends first, since they manage the nftables/iptables rules underneath
+    let probes = [
+        probe_ufw(ctx),
+        probe_firewalld(ctx),
+        probe_nftables(ctx),
+        probe_iptables(ctx),
+    ];
+
+    let mut inactive = Vec::new();
+    let mut unreadable = Vec::new();
+    for probe in probes {
+        match probe {
+            Probe::Found(firewall) if firewall.active => return Ok(active(firewall)),
+            Probe::Found(firewall) => inactive.push(firewall),
+            Probe::Unreadable(evidence) => unreadable.push(evidence),
+            Probe::Missing => {}
+        }
+    }
+
+    if let Some(firewall) = inactive.first() {
+        let mut outcome =
+            CheckOutcome::fail(format!("{} is installed but not active", firewall.name))
+                .with_value(firewall.name)
+                .with_remediation(enable_hint(firewall.name));
+        for evidence in inactive.into_iter().flat_map(|firewall| firewall.evidence) {
+            outcome = outcome.with_evidence(evidence);
+        }
+        return Ok(outcome);
+    }
+
+    if !unreadable.is_empty() {
+        let mut outcome = CheckOutcome::error(
+            "Could not read the firewall rules, the check may need to run as root",
+        );
+        for evidence in unreadable {
+            outcome = outcome.with_evidence(evidence);
+        }
+        return Err(outcome);
+    }
+
+    Ok(CheckOutcome::fail("No firewall was found")
+        .with_remediation("Install and enable a firewall such as ufw or firewalld"))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn active(firewall: Firewall) -> CheckOutcome {
+    let outcome = match firewall.default_deny {
+        Some(true) => CheckOutcome::pass(format!(
+            "{} is active and denies inbound traffic by default",
+            firewall.name
+        )),
+        Some(false) => CheckOutcome::fail(format!(
+            "{} is active but allows inbound traffic by default",
+            firewall.name
+        ))
+        .with_remediation(deny_hint(firewall.name)),
+        None => CheckOutcome::warn(format!(
+            "{} is active, but its rules could not be read without root",
+            firewall.name
+        )),
+    }
+    .with_value(firewall.name);
+
+    firewall
+        .evidence
+        .into_iter()
+        .fold(outcome, CheckOutcome::with_evidence)
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn enable_hint(name: &str) -> &'static str {
+    match name {
+        "ufw" => "Run `sudo ufw enable`",
+        "firewalld" => "Run `sudo systemctl enable --now firewalld`",
+        _ => "Load a firewall ruleset at boot, e.g. `sudo systemctl enable --now nftables`",
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn deny_hint(name: &str) -> &'static str {
+    match name {
+        "ufw" => "Run `sudo ufw default deny incoming`",
+        "firewalld" => {
+            "Switch the default zone to one whose target is not ACCEPT, e.g. `sudo firewall-cmd --set-default-zone=public`"
+        }
+        "nftables" => "Set `policy drop;` on the input chain",
+        _ => "Run `sudo iptables -P INPUT DROP` after allowing the traffic you need",
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+const UFW_CONF: &str = "/etc/ufw/ufw.conf";
+#[cfg(any(target_os = "linux", test))]
+const UFW_DEFAULTS: &str = "/etc/default/ufw";
+#[cfg(any(target_os = "linux", test))]
+const NFTABLES_CONF: &str = "/etc/nftables.conf";
+/// Rules restored at boot by iptables-persistent and the iptables service.
+#[cfg(any(target_os = "linux", test))]
+const IPTABLES_SAVED: &[(&str, &str)] = &[
+    ("netfilter-persistent", "/etc/iptables/rules.v4"),
+    ("iptables", "/etc/sysconfig/iptables"),
+];
+
+#[cfg(any(target_os = "linux", test))]
+fn probe_ufw(ctx: &CheckContext) -> Probe {
+    let output = match ctx.run("ufw", &["status", "verbose"]) {
+        Ok(output) if output.success() => output,
+        // `ufw status` needs root, but its configuration is world-readable
+        Ok(output) => {
+            return ufw_config(ctx).unwrap_or_else(|| Probe::Unreadable(output.stderr_evidence()));
+        }
+        Err(_) => return ufw_config(ctx).unwrap_or(Probe::Missing),
+    };
+
+    let lines: Vec<String> = output
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("Status:") || line.starts_with("Default:"))
+        .map(str::to_string)
+        .collect();
+    let active = lines.iter().any(|line| line == "Status: active");
+    let default_deny = lines.iter().any(|line| {
+        line.starts_with("Default:")
+            && (line.contains("deny (incoming)") || line.contains("reject (incoming)"))
+    });
+
+    Probe::Found(Firewall {
+        name: "ufw",
+        active,
+        default_deny: Some(default_deny),
+        evidence: vec![output.evidence(&lines)],
+    })
+}
+
+/// ufw's state from `ENABLED` in ufw.conf and the default input policy
+/// `ufw default` writes to /etc/default/ufw.
+#[cfg(any(target_os = "linux", test))]
+fn ufw_config(ctx: &CheckContext) -> Option<Probe> {
+    let setting = |path: &str, key: &str| -> Option<(String, Evidence)> {
+        let contents = ctx.read_file(path).ok()?;
+        let (line, value) = contents
+            .lines()
+            .map(str::trim)
+            .filter_map(|line| Some((line, line.strip_prefix(key)?.strip_prefix('=')?)))
+            .next_back()?;
+        Some((
+            value.trim_matches('"').to_string(),
+            Evidence::file(path, &[line.to_string()]),
+        ))
+    };
+
+    let (enabled, enabled_evidence) = setting(UFW_CONF, "ENABLED")?;
+    let mut evidence = vec![enabled_evidence];
+    let default_deny =
+        setting(UFW_DEFAULTS, "DEFAULT_INPUT_POLICY").map(|(policy, policy_evidence)| {
+            evidence.push(policy_evidence);
+            matches!(policy.as_str(), "DROP" | "REJECT")
+        });
+
+    Some(Probe::Found(Firewall {
+        name: "ufw",
+        active: enabled == "yes",
+        default_deny,
+        evidence,
+    }))
+}
+
+/// `systemctl is-active` for a unit, as evidence when it is active.
+#[cfg(any(target_os = "linux", test))]
+pub(super) fn service_active(ctx: &CheckContext, unit: &str) -> Option<Evidence> {
+    let output = ctx.run("systemctl", &["is-active", unit]).ok()?;
+    (output.stdout.trim() == "active").then(|| output.full_evidence())
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn probe_firewalld(ctx: &CheckContext) -> Probe {
+    let Ok(state) = ctx.run("firewall-cmd", &["--state"]) else {
+        return Probe::Missing;
+    };
+    if state.stdout.trim() != "running" {
+        return Probe::Found(Firewall {
+            name: "firewalld",
+            active: false,
+            default_deny: Some(false),
+            evidence: vec![state.full_evidence()],
+        });
+    }
+
+    // Zones with an interface or source bound to them; without any, all
+    // traffic goes through the default zone
+    let zones = match ctx.run("firewall-cmd", &["--get-active-zones"]) {
+        Ok(zones) if zones.success() => zones,
+        Ok(zones) => return Probe::Unreadable(zones.stderr_evidence()),
+        Err(_) => return Probe::Missing,
+    };
+    let mut names: Vec<String> = zones
+        .stdout
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with(char::is_whitespace))
+        .map(|line| line.trim().to_string())
+        .collect();
+    if names.is_empty() {
+        match ctx.run("firewall-cmd", &["--get-default-zone"]) {
+            Ok(zone) if zone.success() => names.push(zone.stdout.trim().to_string()),
+            Ok(zone) => return Probe::Unreadable(zone.stderr_evidence()),
+            Err(_) => return Probe::Missing,
+        }
+    }
+
+    let mut evidence = vec![state.full_evidence(), zones.full_evidence()];
+    let mut default_deny = true;
+    for name in &names {
+        let zone = match ctx.run("firewall-cmd", &[&format!("--zone={}", name), "--list-all"]) {
+            Ok(zone) if zone.success() => zone,
+            Ok(zone) => return Probe::Unreadable(zone.stderr_evidence()),
+            Err(_) => return Probe::Missing,
+        };
+        let target: Vec<String> = zone
+            .stdout
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("target:"))
+            .map(|line| format!("{}: {}", name, line))
+            .collect();
+        // The "default" target rejects anything a zone does not explicitly allow
+        default_deny &= target.first().is_some_and(|line| {
+            let target = line.rsplit("target:").next().unwrap_or_default().trim();
+            matches!(target, "default" | "DROP" | "REJECT" | "%%REJECT%%")
+        });
+        evidence.push(zone.evidence(&target));
+    }
+
+    Probe::Found(Firewall {
+        name: "firewalld",
+        active: true,
+        default_deny: Some(default_deny),
+        evidence,
+    })
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn probe_nftables(ctx: &CheckContext) -> Probe {
+    let Ok(output) = ctx.run("nft", &["list", "ruleset"]) else {
+        return Probe::Missing;
+    };
+    if !output.success() {
+        // Listing the ruleset needs root, so fall back to the ruleset the
+        // nftables service loaded at boot
+        return match service_active(ctx, "nftables") {
+            Some(service) => {
+                let chains = ctx
+                    .read_file(NFTABLES_CONF)
+                    .map(|config| parse_nft_input_chains(&config))
+                    .unwrap_or_default();
+                let mut evidence = vec![service];
+                let default_deny = (!chains.is_empty()).then(|| {
+                    let lines: Vec<String> = chains.iter().map(NftChain::summary).collect();
+                    evidence.push(Evidence::file(NFTABLES_CONF, &lines));
+                    chains.iter().any(NftChain::denies_by_default)
+                });
+                Probe::Found(Firewall {
+                    name: "nftables",
+                    active: true,
+                    default_deny,
+                    evidence,
+                })
+            }
+            None => Probe::Unreadable(output.stderr_evidence()),
+        };
+    }
+
+    let chains = parse_nft_input_chains(&output.stdout);
+    let lines: Vec<String> = chains.iter().map(|chain| chain.summary()).collect();
+
+    Probe::Found(Firewall {
+        name: "nftables",
+        active: !chains.is_empty(),
+        default_deny: Some(chains.iter().any(NftChain::denies_by_default)),
+        evidence: vec![output.evidence(&lines)],
+    })
+}
+
+#[cfg(any(target_os = "linux", test))]
+struct NftChain {
+    name: String,
+    policy: String,
+    last_rule: Option<String>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+impl NftChain {
+    fn denies_by_default(&self) -> bool {
+        self.policy == "drop"
+            || self
+                .last_rule
+                .as_deref()
+                .is_some_and(|rule| rule == "drop" || rule.starts_with("reject"))
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "chain {}: policy {}, last rule: {}",
+            self.name,
+            self.policy,
+            self.last_rule.as_deref().unwrap_or("none")
+        )
+    }
+}
+
+/// Base chains hooked into `input` in the output of `nft list ruleset`.
+#[cfg(any(target_os = "linux", test))]
+fn parse_nft_input_chains(ruleset: &str) -> Vec<NftChain> {
+    let mut chains = Vec::new();
+    let mut current: Option<NftChain> = None;
+
+    for line in ruleset.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix("chain ") {
+            current = Some(NftChain {
+                name: name.trim_end_matches('{').trim().to_string(),
+                policy: String::new(),
+                last_rule: None,
+            });
+            continue;
+        }
+        let Some(chain) = current.as_mut() else {
+            continue;
+        };
+
+        if line == "}" {
+            let chain = current.take().unwrap();
+            if !chain.policy.is_empty() {
+                chains.push(chain);
+            }
+        } else if line.starts_with("type filter hook input") {
+            chain.policy = line
+                .split("policy ")
+                .nth(1)
+                .map(|policy| policy.trim_end_matches(';').trim())
+                .unwrap_or("accept")
+                .to_string();
+        } else if !line.is_empty() && !line.starts_with('#') {
+            chain.last_rule = Some(line.to_string());
+        }
+    }
+
+    chains
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn probe_iptables(ctx: &CheckContext) -> Probe {
+    let Ok(output) = ctx.run("iptables", &["-S", "INPUT"]) else {
+        return Probe::Missing;
+    };
+    if output.success() {
+        let rules = input_rules(&output.stdout);
+        let evidence = vec![output.evidence(&rules)];
+        return Probe::Found(iptables_firewall(&rules, evidence));
+    }
+
+    // Listing the rules needs root, so fall back to the rules restored at
+    // boot, in iptables-save format
+    let Some((service, path)) = IPTABLES_SAVED
+        .iter()
+        .find_map(|(unit, path)| Some((service_active(ctx, unit)?, *path)))
+    else {
+        return Probe::Unreadable(output.stderr_evidence());
+    };
+    match ctx.read_file(path) {
+        Ok(saved) => {
+            let rules = input_rules(&saved);
+            let evidence = vec![service, Evidence::file(path, &rules)];
+            Probe::Found(Firewall {
+                active: true,
+                ..iptables_firewall(&rules, evidence)
+            })
+        }
+        Err(_) => Probe::Found(Firewall {
+            name: "iptables",
+            active: true,
+            default_deny: None,
+            evidence: vec![service],
+        }),
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn iptables_firewall(rules: &[String], evidence: Vec<Evidence>) -> Firewall {
+    let drop_policy = rules.iter().any(|rule| rule == "-P INPUT DROP");
+    let catch_all = rules
+        .last()
+        .is_some_and(|rule| rule == "-A INPUT -j DROP" || rule.starts_with("-A INPUT -j REJECT"));
+
+    Firewall {
+        name: "iptables",
+        active: drop_policy || rules.iter().any(|rule| rule.starts_with("-A INPUT")),
+        default_deny: Some(drop_policy || catch_all),
+        evidence,
+    }
+}
+
+/// The INPUT chain of the filter table from `iptables -S` or iptables-save
+/// output, where the policy is written `:INPUT DROP [0:0]` instead of
+/// `-P INPUT DROP`.
+#[cfg(any(target_os = "linux", test))]
+fn input_rules(rules: &str) -> Vec<String> {
+    let mut in_filter = true;
+    rules
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| {
+            if let Some(table) = line.strip_prefix('*') {
+                in_filter = table == "filter";
+            }
+            if !in_filter {
+                return None;
+            }
+            if let Some(policy) = line.strip_prefix(":INPUT ") {
+                let policy = policy.split_whitespace().next().unwrap_or_default();
+                return Some(format!("-P INPUT {}", policy));
+            }
+            (line.starts_with("-P INPUT") || line.starts_with("-A INPUT")).then(|| line.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    const NOT_ROOT: &str = "ERROR: You need to be root to run this script\n";
+
+    fn context(runner: FixtureRunner) -> CheckContext {
+        context_in("linux/empty", runner)
+    }
+
+    fn context_in(root: &str, runner: FixtureRunner) -> CheckContext {
+        CheckContext::new(runner).with_root(fixture_root(root))
+    }
+
+    #[test]
+    fn ufw_with_default_deny_passes() {
+        let ctx = context(FixtureRunner::new().with_output(
+            "ufw status verbose",
+            include_str!("fixtures/linux/ufw_status_active.txt"),
+        ));
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("ufw"));
+        assert_eq!(
+            outcome.evidence[0].lines,
+            [
+                "Status: active",
+                "Default: deny (incoming), allow (outgoing), disabled (routed)"
+            ]
+        );
+    }
+
+    #[test]
+    fn inactive_ufw_falls_through_to_nftables() {
+        let ctx = context(
+            FixtureRunner::new()
+                .with_output("ufw status verbose", "Status: inactive\n")
+                .with_output(
+                    "nft list ruleset",
+                    include_str!("fixtures/linux/nft_list_ruleset_drop.txt"),
+                ),
+        );
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("nftables"));
+    }
+
+    #[test]
+    fn inactive_ufw_alone_fails() {
+        let ctx = context(
+            FixtureRunner::new()
+                .with_output("ufw status verbose", "Status: inactive\n")
+                .with_output("nft list ruleset", ""),
+        );
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(outcome.message, "ufw is installed but not active");
+        assert_eq!(
+            outcome.remediation.as_deref(),
+            Some("Run `sudo ufw enable`")
+        );
+    }
+
+    #[test]
+    fn firewalld_reads_zone_target() {
+        let ctx = context(
+            FixtureRunner::new()
+                .with_output("firewall-cmd --state", "running\n")
+                .with_output(
+                    "firewall-cmd --get-active-zones",
+                    "FedoraWorkstation\n  interfaces: wlp2s0\n",
+                )
+                .with_output(
+                    "firewall-cmd --zone=FedoraWorkstation --list-all",
+                    include_str!("fixtures/linux/firewall_cmd_list_all.txt"),
+                ),
+        );
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("firewalld"));
+        assert_eq!(
+            outcome.evidence[2].lines,
+            ["FedoraWorkstation: target: default"]
+        );
+    }
+
+    #[test]
+    fn firewalld_checks_every_active_zone() {
+        let ctx = context(
+            FixtureRunner::new()
+                .with_output("firewall-cmd --state", "running\n")
+                .with_output(
+                    "firewall-cmd --get-active-zones",
+                    "FedoraWorkstation\n  interfaces: wlp2s0\ntrusted\n  interfaces: docker0\n",
+                )
+                .with_output(
+                    "firewall-cmd --zone=FedoraWorkstation --list-all",
+                    include_str!("fixtures/linux/firewall_cmd_list_all.txt"),
+                )
+                .with_output(
+                    "firewall-cmd --zone=trusted --list-all",
+                    "trusted (active)\n  target: ACCEPT\n  interfaces: docker0\n",
+                ),
+        );
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(outcome.evidence[3].lines, ["trusted: target: ACCEPT"]);
+    }
+
+    #[test]
+    fn unprivileged_ufw_reads_its_configuration() {
+        let ctx = context_in(
+            "linux/firewall_config",
+            FixtureRunner::new().with_exit("ufw status verbose", 1, "", NOT_ROOT),
+        );
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("ufw"));
+        assert_eq!(outcome.evidence[0].lines, ["ENABLED=yes"]);
+        assert_eq!(outcome.evidence[1].lines, ["DEFAULT_INPUT_POLICY=\"DROP\""]);
+    }
+
+    #[test]
+    fn unprivileged_iptables_reads_saved_rules() {
+        let ctx = context_in(
+            "linux/firewall_config",
+            FixtureRunner::new()
+                .with_exit(
+                    "iptables -S INPUT",
+                    4,
+                    "",
+                    "iptables v1.8.9 (nf_tables): Could not fetch rule set generation id: Permission denied (you must be root)\n",
+                )
+                .with_output("systemctl is-active netfilter-persistent", "active\n"),
+        );
+
+        let probe = probe_iptables(&ctx);
+        let Probe::Found(firewall) = probe else {
+            panic!("iptables was not found");
+        };
+        assert!(firewall.active);
+        assert_eq!(firewall.default_deny, Some(true));
+        assert_eq!(
+            firewall.evidence[1].lines,
+            [
+                "-P INPUT DROP",
+                "-A INPUT -i lo -j ACCEPT",
+                "-A INPUT -m conntrack --ctstate RELATED,ESTABLISHED -j ACCEPT"
+            ]
+        );
+    }
+
+    #[test]
+    fn active_nftables_service_without_readable_rules_warns() {
+        let ctx = context(
+            FixtureRunner::new()
+                .with_exit(
+                    "nft list ruleset",
+                    1,
+                    "",
+                    "Error: Operation not permitted\n",
+                )
+                .with_output("systemctl is-active nftables", "active\n"),
+        );
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Warn);
+        assert_eq!(
+            outcome.message,
+            "nftables is active, but its rules could not be read without root"
+        );
+    }
+
+    #[test]
+    fn iptables_accept_policy_fails() {
+        let ctx = context(FixtureRunner::new().with_output(
+            "iptables -S INPUT",
+            "-P INPUT ACCEPT\n-A INPUT -p tcp -m tcp --dport 22 -j ACCEPT\n",
+        ));
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "iptables is active but allows inbound traffic by default"
+        );
+    }
+
+    #[test]
+    fn unreadable_rules_are_an_error() {
+        let ctx = context(
+            FixtureRunner::new()
+                .with_exit(
+                    "nft list ruleset",
+                    1,
+                    "",
+                    "Error: Operation not permitted\n",
+                )
+                .with_exit(
+                    "iptables -S INPUT",
+                    4,
+                    "",
+                    "iptables v1.8.9 (nf_tables): Could not fetch rule set generation id: Permission denied (you must be root)\n",
+                ),
+        );
+
+        let outcome = check_linux(&ctx).unwrap_err();
+        assert_eq!(outcome.status, CheckStatus::Error);
+        assert_eq!(outcome.evidence.len(), 2);
+    }
+
+    #[test]
+    fn no_firewall_fails() {
+        let outcome = check_linux(&context(FixtureRunner::new())).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(outcome.message, "No firewall was found");
+    }
+
+    #[test]
+    fn parses_nft_input_chains() {
+        let chains =
+            parse_nft_input_chains(include_str!("fixtures/linux/nft_list_ruleset_drop.txt"));
+
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].name, "input");
+        assert_eq!(chains[0].policy, "drop");
+        assert!(chains[0].denies_by_default());
+    }
+}
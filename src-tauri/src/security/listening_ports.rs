@@ -0,0 +1,353 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use serde::{Deserialize, Serialize};
+#[cfg(any(target_os = "linux", test))]
+use std::collections::HashMap;
+#[cfg(any(target_os = "linux", test))]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+pub struct ListeningPortsCheck;
+
+/// A socket that accepts traffic from other hosts.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ListeningSocket {
+    /// `tcp` or `udp`.
+    pub protocol: String,
+    pub address: String,
+    pub port: u16,
+    pub process: Option<String>,
+    pub pid: Option<u32>,
+    pub allowed: bool,
+}
+
+#[async_trait]
+impl SecurityCheck for ListeningPortsCheck {
+    fn id(&self) -> &'static str {
+        "listening_ports"
+    }
+
+    fn title(&self) -> &'static str {
+        "Listening Ports Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying only allowed ports are exposed to the network"
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Listening port detection is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+/// Socket states from the kernel's `tcp_states.h`.
+#[cfg(any(target_os = "linux", test))]
+const TCP_LISTEN: &str = "0A";
+#[cfg(any(target_os = "linux", test))]
+const UDP_UNCONNECTED: &str = "07";
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    // Unconnected UDP sockets on ephemeral ports are clients waiting for replies
+    let ephemeral = ctx
+        .read_file("/proc/sys/net/ipv4/ip_local_port_range")
+        .ok()
+        .and_then(|range| {
+            let mut bounds = range.split_whitespace().map(|bound| bound.parse::<u16>());
+            Some((bounds.next()?.ok()?, bounds.next()?.ok()?))
+        })
+        .unwrap_or((32768, 60999));
+
+    let mut sockets = Vec::new();
+    let mut read = 0;
+    for (table, protocol, state) in [
+        ("tcp", "tcp", TCP_LISTEN),
+        ("tcp6", "tcp", TCP_LISTEN),
+        ("udp", "udp", UDP_UNCONNECTED),
+        ("udp6", "udp", UDP_UNCONNECTED),
+    ] {
+        // tcp6 and udp6 are missing when IPv6 is disabled
+        let Ok(contents) = ctx.read_file(&format!("/proc/net/{}", table)) else {
+            continue;
+        };
+        read += 1;
+        for (address, port, inode) in parse_sockets(&contents, state) {
+            let ephemeral_port = port >= ephemeral.0 && port <= ephemeral.1;
+            if is_loopback(&address) || (protocol == "udp" && ephemeral_port) {
+                continue;
+            }
+            sockets.push((protocol, address, port, inode));
+        }
+    }
+    if read == 0 {
+        return Err(CheckOutcome::error(
+            "Could not read /proc/net/tcp or /proc/net/udp",
+        ));
+    }
+
+    let owners = socket_owners(ctx);
+    let policy = ctx.policy();
+    let mut listening: Vec<ListeningSocket> = sockets
+        .into_iter()
+        .map(|(protocol, address, port, inode)| {
+            let owner = owners.get(&inode);
+            let allowlist = match protocol {
+                "tcp" => &policy.allowed_tcp_ports,
+                _ => &policy.allowed_udp_ports,
+            };
+            ListeningSocket {
+                protocol: protocol.to_string(),
+                address: address.to_string(),
+                port,
+                process: owner.map(|(_, name)| name.clone()),
+                pid: owner.map(|(pid, _)| *pid),
+                allowed: allowlist.contains(&port),
+            }
+        })
+        .collect();
+    listening.sort_by(|a, b| (&a.protocol, a.port).cmp(&(&b.protocol, b.port)));
+
+    let lines: Vec<String> = listening.iter().map(describe).collect();
+    let mut blocked: Vec<String> = listening
+        .iter()
+        .filter(|socket| !socket.allowed)
+        .map(port_name)
+        .collect();
+    blocked.dedup();
+    // A service bound to both 0.0.0.0 and :: exposes one port
+    let mut ports: Vec<(&str, u16)> = listening
+        .iter()
+        .map(|socket| (socket.protocol.as_str(), socket.port))
+        .collect();
+    ports.dedup();
+
+    let outcome = if listening.is_empty() {
+        CheckOutcome::pass("No ports are exposed to the network")
+    } else if blocked.is_empty() {
+        CheckOutcome::pass(format!(
+            "{} exposed ports, all of them allowed",
+            ports.len()
+        ))
+    } else {
+        CheckOutcome::fail(format!(
+            "Ports outside the allowlist are exposed: {}",
+            blocked.join(", ")
+        ))
+        .with_remediation(
+            "Stop the services listening on these ports, bind them to localhost, or add the ports to the allowlist",
+        )
+    };
+
+    Ok(outcome
+        .with_value(ports.len().to_string())
+        .with_evidence(Evidence::file("/proc/net", &lines))
+        .with_details(&listening))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn port_name(socket: &ListeningSocket) -> String {
+    match &socket.process {
+        Some(process) => format!("{}/{} ({})", socket.protocol, socket.port, process),
+        None => format!("{}/{}", socket.protocol, socket.port),
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn describe(socket: &ListeningSocket) -> String {
+    let address = match socket.address.parse::<IpAddr>() {
+        Ok(IpAddr::V6(_)) => format!("[{}]", socket.address),
+        _ => socket.address.clone(),
+    };
+    format!(
+        "{} {}:{} {}",
+        socket.protocol,
+        address,
+        socket.port,
+        socket.process.as_deref().unwrap_or("-")
+    )
+}
+
+/// TCP ports something listens on, on any address including loopback.
+#[cfg(any(target_os = "linux", test))]
+pub(super) fn listening_tcp_ports(ctx: &CheckContext) -> Vec<u16> {
+    ["tcp", "tcp6"]
+        .iter()
+        .filter_map(|table| ctx.read_file(&format!("/proc/net/{}", table)).ok())
+        .flat_map(|contents| parse_sockets(&contents, TCP_LISTEN))
+        .map(|(_, port, _)| port)
+        .collect()
+}
+
+/// Local address, port and inode of the sockets in the given state.
+#[cfg(any(target_os = "linux", test))]
+fn parse_sockets(contents: &str, state: &str) -> Vec<(IpAddr, u16, u64)> {
+    //   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+    //    0: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21234 ...
+    contents
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.get(3) != Some(&state) {
+                return None;
+            }
+            let (address, port) = fields.get(1)?.split_once(':')?;
+            Some((
+                parse_address(address)?,
+                u16::from_str_radix(port, 16).ok()?,
+                fields.get(9)?.parse().ok()?,
+            ))
+        })
+        .collect()
+}
+
+/// The kernel prints addresses as 32-bit words in host byte order.
+#[cfg(any(target_os = "linux", test))]
+fn parse_address(hex: &str) -> Option<IpAddr> {
+    let mut bytes = Vec::with_capacity(16);
+    for word in 0..hex.len() / 8 {
+        let word = u32::from_str_radix(hex.get(word * 8..word * 8 + 8)?, 16).ok()?;
+        bytes.extend(word.to_ne_bytes());
+    }
+
+    match bytes.len() {
+        4 => Some(IpAddr::V4(Ipv4Addr::new(
+            bytes[0], bytes[1], bytes[2], bytes[3],
+        ))),
+        16 => Some(IpAddr::V6(Ipv6Addr::from(
+            <[u8; 16]>::try_from(bytes).ok()?,
+        ))),
+        _ => None,
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn is_loopback(address: &IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => address.is_loopback(),
+        IpAddr::V6(address) => {
+            address.is_loopback() || address.to_ipv4_mapped().is_some_and(|v4| v4.is_loopback())
+        }
+    }
+}
+
+/// Maps socket inodes to the pid and name of the process holding them.
+/// Without root only our own processes can be inspected.
+#[cfg(any(target_os = "linux", test))]
+fn socket_owners(ctx: &CheckContext) -> HashMap<u64, (u32, String)> {
+    let mut owners = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(ctx.path("/proc")) else {
+        return owners;
+    };
+
+    for entry in entries.flatten() {
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        let name = std::fs::read_to_string(entry.path().join("comm"))
+            .map(|comm| comm.trim().to_string())
+            .unwrap_or_default();
+
+        for fd in fds.flatten() {
+            let Ok(target) = std::fs::read_link(fd.path()) else {
+                continue;
+            };
+            let inode = target
+                .to_string_lossy()
+                .strip_prefix("socket:[")
+                .and_then(|inode| inode.strip_suffix(']'))
+                .and_then(|inode| inode.parse().ok());
+            if let Some(inode) = inode {
+                owners.insert(inode, (pid, name.clone()));
+            }
+        }
+    }
+    owners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, Policy, fixture_root};
+
+    fn context() -> CheckContext {
+        CheckContext::new(FixtureRunner::new()).with_root(fixture_root("linux/ports"))
+    }
+
+    #[test]
+    fn lists_exposed_sockets_with_their_processes() {
+        let outcome = check_linux(&context()).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "Ports outside the allowlist are exposed: tcp/22 (sshd), tcp/8080 (python3)"
+        );
+        assert_eq!(
+            outcome.evidence[0].lines,
+            [
+                "tcp 0.0.0.0:22 sshd",
+                "tcp [::]:22 sshd",
+                "tcp 192.168.2.11:8080 python3",
+                "udp 0.0.0.0:68 -",
+                "udp 0.0.0.0:5353 avahi-daemon",
+            ]
+        );
+    }
+
+    #[test]
+    fn allowlisted_ports_pass() {
+        let ctx = context().with_policy(Policy {
+            allowed_tcp_ports: vec![22, 8080],
+            ..Policy::default()
+        });
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        // sshd listens on 0.0.0.0:22 and [::]:22, which is one port
+        assert_eq!(outcome.message, "4 exposed ports, all of them allowed");
+        assert_eq!(outcome.value.as_deref(), Some("4"));
+        assert_eq!(outcome.evidence[0].lines.len(), 5);
+    }
+
+    #[test]
+    fn parses_kernel_addresses() {
+        assert_eq!(
+            parse_address("0B02A8C0"),
+            Some("192.168.2.11".parse().unwrap())
+        );
+        assert_eq!(
+            parse_address("00000000000000000000000001000000"),
+            Some("::1".parse().unwrap())
+        );
+        assert!(is_loopback(
+            &parse_address("0000000000000000FFFF00000100007F").unwrap()
+        ));
+    }
+}
@@ -1,341 +1,191 @@
-use std::process::Command;
-
-#[cfg(target_os = "windows")]
-use std::os::windows::process::CommandExt;
-
-#[tauri::command]
-pub async fn get_antivirus_info() -> Result<Option<String>, String> {
-    Ok(check_antivirus().await)
-}
-
-#[tauri::command]
-pub async fn get_disk_encryption_info() -> Result<Option<String>, String> {
-    Ok(check_disk_encryption().await)
+mod access_control;
+mod accounts;
+mod antivirus;
+mod autologin;
+mod browsers;
+mod commands;
+mod context;
+mod disk_encryption;
+mod firewall;
+mod listening_ports;
+mod os_support;
+mod outcome;
+mod policy;
+mod removable_storage;
+mod runner;
+mod screen_lock;
+mod secure_boot;
+mod sshd;
+mod sudoers;
+mod swap;
+mod sysctl;
+mod time_sync;
+mod updates;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub use access_control::AccessControlCheck;
+pub use accounts::AccountsCheck;
+pub use antivirus::AntivirusCheck;
+pub use autologin::AutologinCheck;
+pub use browsers::{BrowserInstall, BrowsersCheck};
+pub use commands::{get_policy, list_checks, run_check, set_policy};
+pub use context::CheckContext;
+#[cfg(test)]
+pub use context::fixture_root;
+pub use disk_encryption::DiskEncryptionCheck;
+pub use firewall::FirewallCheck;
+pub use listening_ports::ListeningPortsCheck;
+pub use os_support::{OsSupportCheck, OsSupportStatus};
+pub use outcome::{CheckOutcome, CheckStatus, Evidence};
+pub use policy::{Policy, PolicyStore};
+pub use removable_storage::RemovableStorageCheck;
+#[cfg(test)]
+pub use runner::FixtureRunner;
+pub use runner::{CommandRunner, SystemRunner};
+pub use screen_lock::ScreenLockCheck;
+pub use secure_boot::SecureBootCheck;
+pub use sshd::SshdCheck;
+pub use sudoers::SudoersCheck;
+pub use swap::SwapCheck;
+pub use sysctl::SysctlCheck;
+pub use time_sync::TimeSyncCheck;
+pub use updates::{UpdateStatus, UpdatesCheck};
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Malware,
+    Encryption,
+    Access,
+    Network,
+    Updates,
 }
 
-#[tauri::command]
-pub async fn get_screen_lock_info() -> Result<Option<u32>, String> {
-    Ok(check_screen_lock().await)
-}
-
-#[cfg(target_os = "windows")]
-pub async fn check_antivirus() -> Option<String> {
-    let output = Command::new("wmic")
-        .args(&[
-            "/node:localhost",
-            "/namespace:\\\\root\\SecurityCenter2",
-            "path",
-            "AntiVirusProduct",
-            "Get",
-            "DisplayName",
-        ])
-        .creation_flags(0x08000000)
-        .output()
-        .ok()?;
-
-    let result = String::from_utf8_lossy(&output.stdout);
-    let antivirus_names: Vec<String> = result
-        .lines()
-        .filter(|s| !s.trim().eq_ignore_ascii_case("DisplayName"))
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect();
-
-    if antivirus_names.is_empty() {
-        None
-    } else {
-        Some(antivirus_names.join(", "))
-    }
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    Windows,
+    Macos,
+    Linux,
 }
 
-#[cfg(target_os = "macos")]
-pub async fn check_antivirus() -> Option<String> {
-    // Check for XProtect/MRT on macOS
-    let queries = [
-        "SELECT * FROM xprotect_entries;",
-        "SELECT * FROM xprotect_meta;",
-        "SELECT * FROM launchd WHERE name LIKE '%com.apple.MRT%' OR name LIKE '%com.apple.XProtect%';",
-        "SELECT * FROM processes WHERE name LIKE '%MRT%' OR name LIKE '%XProtect%';",
-    ];
+impl Platform {
+    pub const ALL: &'static [Platform] = &[Platform::Windows, Platform::Macos, Platform::Linux];
 
-    for query in queries.iter() {
-        let output = Command::new("sqlite3")
-            .arg("/var/db/SystemPolicyConfiguration/XProtect.bundle/Contents/Resources/XProtect.meta.plist")
-            .arg(query)
-            .output()
-            .ok()?;
-
-        if !output.stdout.is_empty() {
-            return Some("XProtect/MRT (Built-in macOS protection)".to_string());
+    pub fn current() -> Option<Platform> {
+        if cfg!(target_os = "windows") {
+            Some(Platform::Windows)
+        } else if cfg!(target_os = "macos") {
+            Some(Platform::Macos)
+        } else if cfg!(target_os = "linux") {
+            Some(Platform::Linux)
+        } else {
+            None
         }
     }
-    None
-}
-
-#[cfg(target_os = "linux")]
-pub async fn check_antivirus() -> Option<String> {
-    let output = Command::new("systemctl")
-        .args(&["list-units", "--type=service", "--state=running"])
-        .output()
-        .ok()?;
-
-    let result = String::from_utf8_lossy(&output.stdout);
-    let processes: Vec<String> = result
-        .lines()
-        .filter(|line| {
-            let line = line.to_lowercase();
-            line.contains("clamav")
-                || line.contains("sophos")
-                || line.contains("eset")
-                || line.contains("comodo")
-                || line.contains("avg")
-                || line.contains("avast")
-                || line.contains("bitdefender")
-        })
-        .map(|s| s.trim().to_string())
-        .collect();
-
-    if processes.is_empty() {
-        None
-    } else {
-        Some(processes.join(", "))
-    }
-}
-
-#[cfg(target_os = "windows")]
-pub async fn check_disk_encryption() -> Option<String> {
-    let output = Command::new("powershell")
-        .args(&["-Command", "(New-Object -ComObject Shell.Application).NameSpace('C:').Self.ExtendedProperty('System.Volume.BitLockerProtection')"])
-        .creation_flags(0x08000000)
-        .output()
-        .ok()?;
-
-    let result = String::from_utf8_lossy(&output.stdout).trim().to_string();
-
-    match result.as_str() {
-        "1" => Some("BitLocker".to_string()),
-        "7" => Some("Bitlocker: only space used".to_string()),
-        _ => None,
-    }
 }
 
-#[cfg(target_os = "macos")]
-pub async fn check_disk_encryption() -> Option<String> {
-    let output = Command::new("diskutil")
-        .args(&["info", "/"])
-        .output()
-        .ok()?;
-
-    let result = String::from_utf8_lossy(&output.stdout);
-    if result.contains("FileVault: Yes") {
-        Some("FileVault".to_string())
-    } else {
-        None
-    }
+#[derive(Serialize, Clone, Debug)]
+pub struct CheckInfo {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    pub category: Category,
+    pub platforms: &'static [Platform],
 }
 
-#[cfg(target_os = "linux")]
-pub async fn check_disk_encryption() -> Option<String> {
-    // Check for ecryptfs
-    let ecryptfs_output = Command::new("mount").output().ok()?;
-
-    let ecryptfs_result = String::from_utf8_lossy(&ecryptfs_output.stdout);
-    if ecryptfs_result.contains("ecryptfs") {
-        return Some("ecryptfs".to_string());
+/// A single security check. Implementations are registered in
+/// [`Registry::builtin`] and exposed to the UI through `list_checks` and
+/// `run_check`, so adding a check does not require a new command.
+#[async_trait]
+pub trait SecurityCheck: Send + Sync {
+    fn id(&self) -> &'static str;
+    fn title(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn category(&self) -> Category;
+    fn platforms(&self) -> &'static [Platform];
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome;
+
+    fn is_supported(&self) -> bool {
+        Platform::current().is_some_and(|platform| self.platforms().contains(&platform))
     }
 
-    // Check for LUKS
-    let luks_output = Command::new("lsblk").args(&["-o", "TYPE"]).output().ok()?;
-
-    let luks_result = String::from_utf8_lossy(&luks_output.stdout);
-    if luks_result.contains("crypt") {
-        return Some("LUKS".to_string());
+    fn info(&self) -> CheckInfo {
+        CheckInfo {
+            id: self.id(),
+            title: self.title(),
+            description: self.description(),
+            category: self.category(),
+            platforms: self.platforms(),
+        }
     }
-
-    None
 }
 
-#[cfg(target_os = "windows")]
-pub async fn check_screen_lock() -> Option<u32> {
-    let output = Command::new("powershell")
-        .args(&["-Command", r#"
-            $lang = (Get-WinUserLanguageList).LocalizedName.Split(' ')[0].ToLower();
-            $acPattern = if ($lang -eq 'spanish') { 'Índice de configuración de corriente alterna actual' } else { 'Current AC Power Setting Index' };
-            $dcPattern = if ($lang -eq 'spanish') { 'Índice de configuración de corriente continua actual' } else { 'Current DC Power Setting Index' };
-
-            $acSettings = (powercfg -q SCHEME_CURRENT SUB_VIDEO VIDEOIDLE | Select-String -Pattern $acPattern).Line.Split(':')[1].Trim();
-            $dcSettings = (powercfg -q SCHEME_CURRENT SUB_VIDEO VIDEOIDLE | Select-String -Pattern $dcPattern).Line.Split(':')[1].Trim();
-            $hasBattery = [bool](Get-CimInstance -ClassName Win32_Battery -ErrorAction SilentlyContinue);
-
-            [PSCustomObject]@{
-                AC = $acSettings;
-                DC = $dcSettings;
-                HasBattery = $hasBattery
-            } | ConvertTo-Json
-        "#])
-        .creation_flags(0x08000000)
-        .output()
-        .ok()?;
-
-    let result = String::from_utf8_lossy(&output.stdout);
-    let settings: serde_json::Value = serde_json::from_str(&result).ok()?;
-
-    let parse_hex = |s: &str| -> Option<u32> {
-        let hex_str = if s.starts_with("0x") { &s[2..] } else { s };
-        u32::from_str_radix(hex_str, 16).ok()
-    };
-
-    let ac_timeout = parse_hex(settings["AC"].as_str()?)?;
-    let dc_timeout = parse_hex(settings["DC"].as_str()?)?;
-    let has_battery = settings["HasBattery"].as_bool()?;
-
-    let timeout = if has_battery {
-        std::cmp::max(ac_timeout, dc_timeout)
-    } else {
-        ac_timeout
-    };
-
-    if timeout == 0 {
-        None
-    } else {
-        Some(timeout / 60)
-    }
+#[derive(Default)]
+pub struct Registry {
+    checks: Vec<Box<dyn SecurityCheck>>,
 }
 
-#[cfg(target_os = "macos")]
-pub async fn check_screen_lock() -> Option<u32> {
-    let output = Command::new("sysadminctl")
-        .args(&["-screenLock", "status"])
-        .output()
-        .ok()?;
-
-    let result = String::from_utf8_lossy(&output.stdout);
-
-    if result.contains("screenLock is off") {
-        return None;
+impl Registry {
+    pub fn builtin() -> Self {
+        Registry::default()
+            .register(AntivirusCheck)
+            .register(DiskEncryptionCheck)
+            .register(SwapCheck)
+            .register(ScreenLockCheck)
+            .register(FirewallCheck)
+            .register(ListeningPortsCheck)
+            .register(TimeSyncCheck)
+            .register(UpdatesCheck)
+            .register(OsSupportCheck)
+            .register(BrowsersCheck)
+            .register(AccessControlCheck)
+            .register(SecureBootCheck)
+            .register(SshdCheck)
+            .register(AccountsCheck)
+            .register(AutologinCheck)
+            .register(SudoersCheck)
+            .register(RemovableStorageCheck)
+            .register(SysctlCheck)
     }
 
-    let screensaver_output = Command::new("defaults")
-        .args(&["-currentHost", "read", "com.apple.screensaver", "idleTime"])
-        .output()
-        .ok()?;
-
-    let screensaver_time = String::from_utf8_lossy(&screensaver_output.stdout)
-        .trim()
-        .parse::<u32>()
-        .ok()?
-        / 60;
-
-    let display_sleep_ac = get_display_sleep("AC Power")?;
-    let display_sleep_battery = get_display_sleep("Battery Power")?;
-
-    let max_timeout = std::cmp::max(
-        screensaver_time,
-        std::cmp::max(display_sleep_ac, display_sleep_battery),
-    );
-
-    if result.contains("screenLock delay is immediate") {
-        return Some(max_timeout);
+    pub fn register(mut self, check: impl SecurityCheck + 'static) -> Self {
+        self.checks.push(Box::new(check));
+        self
     }
 
-    if let Some(caps) = regex::Regex::new(r"screenLock delay is (\d+) seconds")
-        .ok()?
-        .captures(&result)
-    {
-        let screen_lock_delay = caps.get(1)?.as_str().parse::<u32>().ok()? / 60;
-        return Some(max_timeout + screen_lock_delay);
+    /// Checks that support the platform we are running on, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn SecurityCheck> {
+        self.checks
+            .iter()
+            .map(|check| check.as_ref())
+            .filter(|check| check.is_supported())
     }
 
-    None
-}
-
-#[cfg(target_os = "macos")]
-fn get_display_sleep(mode: &str) -> Option<u32> {
-    let output = Command::new("pmset")
-        .args(&["-g", "custom"])
-        .output()
-        .ok()?;
-
-    let result = String::from_utf8_lossy(&output.stdout);
-    let pattern = format!("{}/displaysleep", mode);
-
-    result
-        .lines()
-        .find(|line| line.contains(&pattern))
-        .and_then(|line| line.split_whitespace().last())
-        .and_then(|time| time.parse::<u32>().ok())
-}
+    /// Runs a registered check. Checks that exist but do not support this
+    /// platform are reported as not applicable rather than unknown.
+    pub async fn run(&self, id: &str, ctx: &CheckContext) -> Option<CheckOutcome> {
+        let check = self.checks.iter().find(|check| check.id() == id)?;
 
-#[cfg(target_os = "linux")]
-pub async fn check_screen_lock() -> Option<u32> {
-    let desktop = Command::new("env")
-        .arg("XDG_SESSION_DESKTOP")
-        .output()
-        .ok()
-        .and_then(|output| {
-            String::from_utf8_lossy(&output.stdout)
-                .trim()
-                .split('=')
-                .nth(1)
-                .map(|s| s.to_string())
-        })?;
-
-    let desktop = if desktop == "ubuntu" {
-        "gnome".to_string()
-    } else if desktop == "awesome" {
-        let sessions = Command::new("ls").arg("/usr/bin/*session").output().ok()?;
-
-        if String::from_utf8_lossy(&sessions.stdout).contains("gnome") {
-            "gnome".to_string()
+        if check.is_supported() {
+            Some(check.run(ctx).await)
         } else {
-            desktop
+            Some(CheckOutcome::not_applicable(format!(
+                "{} is not available on this platform",
+                check.title()
+            )))
         }
-    } else {
-        desktop
-    };
-
-    let lock_enabled = Command::new("gsettings")
-        .args(&[
-            "get",
-            &format!("org.{}.desktop.screensaver", desktop),
-            "lock-enabled",
-        ])
-        .output()
-        .ok()?;
-
-    if String::from_utf8_lossy(&lock_enabled.stdout).trim() != "true" {
-        return None;
     }
 
-    let idle_delay = Command::new("gsettings")
-        .args(&[
-            "get",
-            &format!("org.{}.desktop.session", desktop),
-            "idle-delay",
-        ])
-        .output()
-        .ok()?;
-
-    let lock_delay = Command::new("gsettings")
-        .args(&[
-            "get",
-            &format!("org.{}.desktop.screensaver", desktop),
-            "lock-delay",
-        ])
-        .output()
-        .ok()?;
-
-    let idle_seconds = String::from_utf8_lossy(&idle_delay.stdout)
-        .trim()
-        .split_whitespace()
-        .nth(1)
-        .and_then(|s| s.parse::<u32>().ok())?;
-
-    let lock_seconds = String::from_utf8_lossy(&lock_delay.stdout)
-        .trim()
-        .split_whitespace()
-        .nth(1)
-        .and_then(|s| s.parse::<u32>().ok())?;
-
-    Some((idle_seconds + lock_seconds) / 60)
+    /// Runs every check supported on this platform, keyed by check id.
+    pub async fn run_all(&self, ctx: &CheckContext) -> BTreeMap<String, CheckOutcome> {
+        let mut outcomes = BTreeMap::new();
+        for check in self.iter() {
+            outcomes.insert(check.id().to_string(), check.run(ctx).await);
+        }
+        outcomes
+    }
 }
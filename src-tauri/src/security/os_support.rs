@@ -0,0 +1,395 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use chrono::NaiveDate;
+
+pub struct OsSupportCheck;
+
+/// The running distribution and kernel, and whether they still receive
+/// security fixes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct OsSupportStatus {
+    /// PRETTY_NAME from /etc/os-release, e.g. `Ubuntu 22.04.4 LTS`.
+    pub distribution: String,
+    pub id: String,
+    pub version_id: Option<String>,
+    pub codename: Option<String>,
+    /// End of security support, `None` for rolling or unknown releases.
+    pub eol: Option<String>,
+    pub rolling: bool,
+    /// The running kernel release, as printed by `uname -r`.
+    pub kernel: Option<String>,
+    /// Whether the kernel series is no longer maintained on kernel.org.
+    pub kernel_eol: bool,
+}
+
+#[async_trait]
+impl SecurityCheck for OsSupportCheck {
+    fn id(&self) -> &'static str {
+        "os_support"
+    }
+
+    fn title(&self) -> &'static str {
+        "Operating System Support Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying the distribution and kernel still receive security fixes"
+    }
+
+    fn category(&self) -> Category {
+        Category::Updates
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx, chrono::Local::now().date_naive());
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "End of life detection is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+/// A release from the embedded `os_eol.toml` table.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize)]
+struct Release {
+    id: String,
+    version: Option<String>,
+    codename: Option<String>,
+    eol: Option<NaiveDate>,
+    #[serde(default)]
+    rolling: bool,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize)]
+struct Kernel {
+    series: String,
+    eol: NaiveDate,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize)]
+struct EolTable {
+    releases: Vec<Release>,
+    kernels: Vec<Kernel>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn eol_table() -> EolTable {
+    toml::from_str(include_str!("os_eol.toml")).expect("valid end of life table")
+}
+
+#[cfg(any(target_os = "linux", test))]
+const OS_RELEASE: &str = "/etc/os-release";
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext, today: NaiveDate) -> Result<CheckOutcome, CheckOutcome> {
+    // /usr/lib/os-release is the fallback the specification defines
+    let (path, os_release) = [OS_RELEASE, "/usr/lib/os-release"]
+        .iter()
+        .find_map(|path| Some((*path, ctx.read_file(path).ok()?)))
+        .ok_or_else(|| CheckOutcome::error(format!("Could not read {}", OS_RELEASE)))?;
+    let fields = parse_os_release(&os_release);
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+            .filter(|value| !value.is_empty())
+    };
+
+    let id = field("ID").unwrap_or_else(|| "linux".to_string());
+    let mut status = OsSupportStatus {
+        distribution: field("PRETTY_NAME")
+            .or_else(|| field("NAME"))
+            .unwrap_or_else(|| id.clone()),
+        id: id.clone(),
+        version_id: field("VERSION_ID"),
+        codename: field("VERSION_CODENAME").or_else(|| field("UBUNTU_CODENAME")),
+        ..OsSupportStatus::default()
+    };
+    let mut evidence = vec![Evidence::file(
+        path,
+        &["ID", "ID_LIKE", "VERSION_ID", "VERSION_CODENAME"]
+            .iter()
+            .filter_map(|name| Some(format!("{}={}", name, field(name)?)))
+            .collect::<Vec<_>>(),
+    )];
+
+    let table = eol_table();
+    let mut ids = vec![id];
+    ids.extend(
+        field("ID_LIKE")
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_string),
+    );
+    let codenames: Vec<String> = [field("UBUNTU_CODENAME"), field("VERSION_CODENAME")]
+        .into_iter()
+        .flatten()
+        .collect();
+    let release = find_release(
+        &table.releases,
+        &ids,
+        status.version_id.as_deref(),
+        &codenames,
+    );
+    // Distributions missing from the table may announce their own date
+    let release_eol = match release {
+        Some(release) => release.eol,
+        None => field("SUPPORT_END").and_then(|date| date.parse::<NaiveDate>().ok()),
+    };
+    status.rolling = release.is_some_and(|release| release.rolling);
+    status.eol = release_eol.map(|eol| eol.to_string());
+
+    if let Ok(output) = ctx.run("uname", &["-r"])
+        && output.success()
+    {
+        let kernel = output.stdout.trim().to_string();
+        status.kernel_eol = kernel_eol(&table.kernels, &kernel, today);
+        evidence.push(output.evidence(std::slice::from_ref(&kernel)));
+        status.kernel = Some(kernel);
+    }
+
+    let kernel = status.kernel.as_deref().unwrap_or("unknown");
+    // Supported releases backport fixes to the kernels they ship, so the
+    // kernel.org series only matters for rolling and unknown releases
+    let outcome = match release_eol {
+        Some(eol) if eol < today => CheckOutcome::fail(format!(
+            "{} reached end of life on {}",
+            status.distribution, eol
+        ))
+        .with_remediation("Upgrade to a supported release of the distribution"),
+        Some(eol) => CheckOutcome::pass(format!(
+            "{} is supported until {}",
+            status.distribution, eol
+        )),
+        None if status.kernel_eol => CheckOutcome::fail(format!(
+            "Kernel {} is older than every maintained longterm kernel series",
+            kernel
+        ))
+        .with_remediation("Install a kernel from a maintained longterm series and reboot"),
+        None if status.rolling => CheckOutcome::pass(format!(
+            "{} is a rolling release running kernel {}",
+            status.distribution, kernel
+        )),
+        None => CheckOutcome::warn(format!(
+            "The end of life of {} is unknown",
+            status.distribution
+        )),
+    };
+
+    Ok(evidence
+        .into_iter()
+        .fold(
+            outcome.with_value(status.distribution.as_str()),
+            CheckOutcome::with_evidence,
+        )
+        .with_details(&status))
+}
+
+/// `KEY=value` lines with shell-style quoting removed.
+#[cfg(any(target_os = "linux", test))]
+fn parse_os_release(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'')?.strip_suffix('\''))
+                .unwrap_or(value);
+            (key.to_string(), value.replace("\\\"", "\""))
+        })
+        .collect()
+}
+
+/// The release for the first of `ids` that has one, by version and then
+/// by codename.
+#[cfg(any(target_os = "linux", test))]
+fn find_release<'a>(
+    releases: &'a [Release],
+    ids: &[String],
+    version_id: Option<&str>,
+    codenames: &[String],
+) -> Option<&'a Release> {
+    let version_matches = |release: &Release| match (&release.version, version_id) {
+        (None, _) => release.rolling,
+        (Some(version), Some(version_id)) => {
+            version_id == version
+                || version_id
+                    .strip_prefix(version.as_str())
+                    .is_some_and(|rest| rest.starts_with('.'))
+        }
+        (Some(_), None) => false,
+    };
+    let codename_matches = |release: &Release| {
+        release
+            .codename
+            .as_ref()
+            .is_some_and(|codename| codenames.contains(codename))
+    };
+
+    ids.iter()
+        .find_map(|id| {
+            releases
+                .iter()
+                .find(|release| release.id == *id && version_matches(release))
+        })
+        .or_else(|| {
+            ids.iter().find_map(|id| {
+                releases
+                    .iter()
+                    .find(|release| release.id == *id && codename_matches(release))
+            })
+        })
+}
+
+/// Whether a kernel release such as `6.8.0-45-generic` belongs to a series
+/// kernel.org no longer maintains: a longterm series past its end of life,
+/// or any series older than the oldest maintained one.
+#[cfg(any(target_os = "linux", test))]
+fn kernel_eol(kernels: &[Kernel], release: &str, today: NaiveDate) -> bool {
+    let series = |version: &str| -> Option<(u32, u32)> {
+        let mut parts = version.split(['.', '-']);
+        Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+    };
+    let Some(running) = series(release) else {
+        return false;
+    };
+
+    if let Some(kernel) = kernels
+        .iter()
+        .find(|kernel| series(&kernel.series) == Some(running))
+    {
+        return kernel.eol < today;
+    }
+    kernels
+        .iter()
+        .filter(|kernel| kernel.eol >= today)
+        .filter_map(|kernel| series(&kernel.series))
+        .min()
+        .is_some_and(|oldest| running < oldest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()
+    }
+
+    fn context(root: &str, kernel: &str) -> CheckContext {
+        CheckContext::new(FixtureRunner::new().with_output("uname -r", kernel))
+            .with_root(fixture_root(root))
+    }
+
+    #[test]
+    fn supported_release_passes() {
+        let outcome = check_linux(
+            &context("linux/os_ubuntu_jammy", "5.15.0-122-generic\n"),
+            today(),
+        )
+        .unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.message,
+            "Ubuntu 22.04.4 LTS is supported until 2027-06-01"
+        );
+        assert_eq!(
+            outcome.evidence[0].lines,
+            [
+                "ID=ubuntu",
+                "ID_LIKE=debian",
+                "VERSION_ID=22.04",
+                "VERSION_CODENAME=jammy"
+            ]
+        );
+    }
+
+    #[test]
+    fn end_of_life_release_fails() {
+        let outcome = check_linux(
+            &context("linux/os_debian_bullseye", "5.10.0-32-amd64\n"),
+            today(),
+        )
+        .unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "Debian GNU/Linux 11 (bullseye) reached end of life on 2024-08-14"
+        );
+    }
+
+    #[test]
+    fn derivatives_match_through_id_like() {
+        let outcome = check_linux(
+            &context("linux/os_rocky", "5.14.0-427.13.1.el9_4.x86_64\n"),
+            today(),
+        )
+        .unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        let status: OsSupportStatus = serde_json::from_value(*outcome.details.unwrap()).unwrap();
+        assert_eq!(status.eol.as_deref(), Some("2032-05-31"));
+        assert!(!status.kernel_eol);
+    }
+
+    #[test]
+    fn rolling_release_with_old_kernel_fails() {
+        let outcome = check_linux(&context("linux/os_arch", "5.4.281-1-lts\n"), today()).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "Kernel 5.4.281-1-lts is older than every maintained longterm kernel series"
+        );
+    }
+
+    #[test]
+    fn kernel_series_end_of_life() {
+        let kernels = eol_table().kernels;
+
+        assert!(!kernel_eol(&kernels, "6.16.8-arch1-1", today()));
+        assert!(!kernel_eol(&kernels, "6.6.52", today()));
+        assert!(kernel_eol(&kernels, "5.4.0-196-generic", today()));
+        assert!(kernel_eol(&kernels, "4.18.0-553.el8_10.x86_64", today()));
+    }
+
+    #[test]
+    fn parses_quoted_values() {
+        assert_eq!(
+            parse_os_release("NAME=\"Fedora Linux\"\nVERSION_ID=40\nVARIANT='Workstation'\n"),
+            [
+                ("NAME".to_string(), "Fedora Linux".to_string()),
+                ("VERSION_ID".to_string(), "40".to_string()),
+                ("VARIANT".to_string(), "Workstation".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    Warn,
+    Error,
+    NotApplicable,
+}
+
+/// Raw data a check based its verdict on, so a failure can be diagnosed
+/// without re-running the command by hand.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct Evidence {
+    pub command: String,
+    pub exit_code: Option<i32>,
+    pub lines: Vec<String>,
+}
+
+impl Evidence {
+    /// Evidence read from a file rather than a command's output.
+    pub fn file(path: &str, lines: &[String]) -> Self {
+        Evidence {
+            command: path.to_string(),
+            exit_code: None,
+            lines: lines.to_vec(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CheckOutcome {
+    pub status: CheckStatus,
+    pub message: String,
+    pub value: Option<String>,
+    #[serde(default)]
+    pub evidence: Vec<Evidence>,
+    pub remediation: Option<String>,
+    /// Structured findings for checks that report more than a single value.
+    #[serde(default)]
+    pub details: Option<Box<serde_json::Value>>,
+}
+
+impl CheckOutcome {
+    fn new(status: CheckStatus, message: impl Into<String>) -> Self {
+        CheckOutcome {
+            status,
+            message: message.into(),
+            value: None,
+            evidence: Vec::new(),
+            remediation: None,
+            details: None,
+        }
+    }
+
+    pub fn pass(message: impl Into<String>) -> Self {
+        CheckOutcome::new(CheckStatus::Pass, message)
+    }
+
+    pub fn fail(message: impl Into<String>) -> Self {
+        CheckOutcome::new(CheckStatus::Fail, message)
+    }
+
+    pub fn warn(message: impl Into<String>) -> Self {
+        CheckOutcome::new(CheckStatus::Warn, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        CheckOutcome::new(CheckStatus::Error, message)
+    }
+
+    pub fn not_applicable(message: impl Into<String>) -> Self {
+        CheckOutcome::new(CheckStatus::NotApplicable, message)
+    }
+
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn with_evidence(mut self, evidence: Evidence) -> Self {
+        self.evidence.push(evidence);
+        self
+    }
+
+    pub fn with_remediation(mut self, remediation: impl Into<String>) -> Self {
+        self.remediation = Some(remediation.into());
+        self
+    }
+
+    pub fn with_details(mut self, details: &impl Serialize) -> Self {
+        self.details = serde_json::to_value(details).ok().map(Box::new);
+        self
+    }
+
+    /// Whether the device satisfies the check, possibly with a warning.
+    pub fn is_compliant(&self) -> bool {
+        matches!(self.status, CheckStatus::Pass | CheckStatus::Warn)
+    }
+}
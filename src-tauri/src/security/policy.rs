@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Thresholds the checks compare the device against, so an organisation
+/// can tighten or relax them without a new release.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Policy {
+    /// Age in days after which ClamAV signatures count as outdated.
+    pub clamav_max_signature_age_days: u32,
+    /// Days without an installed update after which the package lists are
+    /// assumed stale, even when nothing is pending.
+    pub max_update_age_days: u32,
+    /// TCP ports services may listen on for other hosts.
+    pub allowed_tcp_ports: Vec<u16>,
+    /// UDP ports services may listen on for other hosts.
+    pub allowed_udp_ports: Vec<u16>,
+    /// Whether USB drives and other removable storage may be used.
+    pub removable_storage_allowed: bool,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy {
+            clamav_max_signature_age_days: 7,
+            max_update_age_days: 30,
+            allowed_tcp_ports: Vec::new(),
+            // DHCP and DHCPv6 clients, and mDNS
+            allowed_udp_ports: vec![68, 546, 5353],
+            removable_storage_allowed: true,
+        }
+    }
+}
+
+/// The policy the app runs checks with, persisted next to its other settings.
+pub struct PolicyStore {
+    path: PathBuf,
+    policy: Mutex<Policy>,
+}
+
+impl PolicyStore {
+    pub fn load(path: PathBuf) -> Self {
+        let policy = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        PolicyStore {
+            path,
+            policy: Mutex::new(policy),
+        }
+    }
+
+    pub fn policy(&self) -> Policy {
+        self.policy.lock().unwrap().clone()
+    }
+
+    pub fn set(&self, policy: Policy) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let serialized = serde_json::to_string_pretty(&policy).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, serialized).map_err(|e| e.to_string())?;
+
+        *self.policy.lock().unwrap() = policy;
+        Ok(())
+    }
+}
@@ -0,0 +1,372 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use serde::{Deserialize, Serialize};
+
+pub struct RemovableStorageCheck;
+
+/// Whether USB mass storage can be used on this device.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct RemovableStorageStatus {
+    pub allowed_by_policy: bool,
+    /// The modprobe.d line that keeps usb_storage from loading, as
+    /// `file: line`.
+    pub module_blocked: Option<String>,
+    pub module_loaded: bool,
+    /// `blocking`, `allowing` or `not running`.
+    pub usbguard: String,
+    pub devices: Vec<RemovableDevice>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RemovableDevice {
+    pub name: String,
+    pub model: Option<String>,
+    pub size_bytes: u64,
+    pub usb: bool,
+}
+
+#[async_trait]
+impl SecurityCheck for RemovableStorageCheck {
+    fn id(&self) -> &'static str {
+        "removable_storage"
+    }
+
+    fn title(&self) -> &'static str {
+        "Removable Storage Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying USB storage is blocked where the policy forbids it"
+    }
+
+    fn category(&self) -> Category {
+        Category::Access
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Removable storage detection is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+/// Directories modprobe reads configuration from, lowest precedence first.
+#[cfg(any(target_os = "linux", test))]
+const MODPROBE_DIRS: &[&str] = &[
+    "/usr/lib/modprobe.d",
+    "/lib/modprobe.d",
+    "/run/modprobe.d",
+    "/etc/modprobe.d",
+];
+#[cfg(any(target_os = "linux", test))]
+const USBGUARD_CONF: &str = "/etc/usbguard/usbguard-daemon.conf";
+#[cfg(any(target_os = "linux", test))]
+const USBGUARD_RULES: &str = "/etc/usbguard/rules.conf";
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let mut status = RemovableStorageStatus {
+        allowed_by_policy: ctx.policy().removable_storage_allowed,
+        module_blocked: module_blocked(ctx),
+        module_loaded: ctx
+            .read_file("/proc/modules")
+            .map(|modules| {
+                modules
+                    .lines()
+                    .any(|module| module.split_whitespace().next() == Some("usb_storage"))
+            })
+            .unwrap_or(false),
+        usbguard: usbguard_state(ctx).to_string(),
+        devices: removable_devices(ctx),
+    };
+    status.devices.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut evidence = Vec::new();
+    if let Some(blocked) = &status.module_blocked {
+        let (file, line) = blocked.split_once(": ").unwrap_or(("", blocked));
+        evidence.push(Evidence::file(file, &[line.to_string()]));
+    }
+    if !status.devices.is_empty() {
+        let lines: Vec<String> = status.devices.iter().map(describe).collect();
+        evidence.push(Evidence::file("/sys/block", &lines));
+    }
+
+    let blocked_by = match (&status.module_blocked, status.usbguard.as_str()) {
+        (Some(_), "blocking") => Some("a modprobe blacklist and USBGuard"),
+        (Some(_), _) => Some("a modprobe blacklist"),
+        (None, "blocking") => Some("USBGuard"),
+        (None, _) => None,
+    };
+    let attached: Vec<String> = status.devices.iter().map(describe).collect();
+
+    let outcome = if status.allowed_by_policy {
+        let message = match attached.len() {
+            0 => "Removable storage is allowed by policy".to_string(),
+            _ => format!(
+                "Removable storage is allowed by policy, attached: {}",
+                attached.join(", ")
+            ),
+        };
+        CheckOutcome::pass(message).with_value("allowed")
+    } else if blocked_by.is_none() {
+        CheckOutcome::fail("USB storage is not blocked, but the policy forbids it")
+            .with_value("unblocked")
+            .with_remediation(
+                "Add `install usb_storage /bin/false` to /etc/modprobe.d/usb-storage.conf, or block storage devices with USBGuard",
+            )
+    } else if !attached.is_empty() {
+        CheckOutcome::fail(format!(
+            "USB storage is blocked by {}, but removable devices are attached: {}",
+            blocked_by.unwrap_or_default(),
+            attached.join(", ")
+        ))
+        .with_value("unblocked")
+        .with_remediation("Detach the removable devices and reboot")
+    } else if status.module_loaded && status.usbguard != "blocking" {
+        CheckOutcome::fail("usb_storage is blacklisted, but the module is already loaded")
+            .with_value("unblocked")
+            .with_remediation("Unload the module with `modprobe -r usb_storage` or reboot")
+    } else {
+        CheckOutcome::pass(format!(
+            "USB storage is blocked by {}",
+            blocked_by.unwrap_or_default()
+        ))
+        .with_value("blocked")
+    };
+
+    Ok(evidence
+        .into_iter()
+        .fold(outcome, CheckOutcome::with_evidence)
+        .with_details(&status))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn describe(device: &RemovableDevice) -> String {
+    match &device.model {
+        Some(model) => format!("{} ({})", device.name, model),
+        None => device.name.clone(),
+    }
+}
+
+/// The last modprobe.d line that keeps usb_storage from loading, either a
+/// `blacklist` or an `install` replaced with a no-op.
+#[cfg(any(target_os = "linux", test))]
+fn module_blocked(ctx: &CheckContext) -> Option<String> {
+    // A file in a later directory replaces one with the same name
+    let mut files: Vec<(String, String)> = Vec::new();
+    for dir in MODPROBE_DIRS {
+        for entry in std::fs::read_dir(ctx.path(dir))
+            .into_iter()
+            .flatten()
+            .flatten()
+        {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.ends_with(".conf") {
+                continue;
+            }
+            files.retain(|(existing, _)| *existing != name);
+            files.push((name.clone(), format!("{}/{}", dir, name)));
+        }
+    }
+    files.sort();
+
+    let mut blocked = None;
+    for (_, path) in files {
+        let Ok(config) = ctx.read_file(&path) else {
+            continue;
+        };
+        for line in config.lines().map(str::trim) {
+            let mut words = line.split_whitespace();
+            let (Some(command), Some(module)) = (words.next(), words.next()) else {
+                continue;
+            };
+            if module.replace('-', "_") != "usb_storage" {
+                continue;
+            }
+            let no_op = |program: &str| {
+                matches!(
+                    program,
+                    "/bin/true" | "/bin/false" | "/usr/bin/true" | "/usr/bin/false"
+                )
+            };
+            match command {
+                "blacklist" => blocked = Some(format!("{}: {}", path, line)),
+                "install" if words.next().is_some_and(no_op) => {
+                    blocked = Some(format!("{}: {}", path, line))
+                }
+                _ => {}
+            }
+        }
+    }
+    blocked
+}
+
+/// Whether a running USBGuard daemon keeps mass storage interfaces
+/// (class 08) out.
+#[cfg(any(target_os = "linux", test))]
+fn usbguard_state(ctx: &CheckContext) -> &'static str {
+    if !ctx.processes().iter().any(|name| name == "usbguard-daemon") {
+        return "not running";
+    }
+
+    let implicit = ctx
+        .read_file(USBGUARD_CONF)
+        .ok()
+        .and_then(|config| {
+            config.lines().find_map(|line| {
+                let (key, value) = line.trim().split_once('=')?;
+                (key.trim() == "ImplicitPolicyTarget").then(|| value.trim().to_string())
+            })
+        })
+        .unwrap_or_else(|| "block".to_string());
+    // The rules are only readable by root; without them any allow rule
+    // could cover storage
+    let storage_allowed = ctx
+        .read_file(USBGUARD_RULES)
+        .map(|rules| {
+            rules.lines().map(str::trim).any(|rule| {
+                rule.starts_with("allow")
+                    && (rule.contains(" 08:")
+                        || rule.contains("{ 08:")
+                        || !rule.contains("with-interface"))
+            })
+        })
+        .unwrap_or(true);
+
+    if implicit != "allow" && !storage_allowed {
+        "blocking"
+    } else {
+        "allowing"
+    }
+}
+
+/// Block devices with removable media inserted, and USB disks, which
+/// often report themselves as fixed.
+#[cfg(any(target_os = "linux", test))]
+fn removable_devices(ctx: &CheckContext) -> Vec<RemovableDevice> {
+    std::fs::read_dir(ctx.path("/sys/block"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            let read = |attribute: &str| {
+                std::fs::read_to_string(path.join(attribute))
+                    .map(|value| value.trim().to_string())
+                    .ok()
+            };
+
+            let usb = std::fs::read_link(&path)
+                .map(|target| target.to_string_lossy().contains("/usb"))
+                .unwrap_or(false);
+            if read("removable").as_deref() != Some("1") && !usb {
+                return None;
+            }
+            // Card readers and optical drives without media report size 0
+            let sectors: u64 = read("size")?.parse().ok()?;
+            if sectors == 0 {
+                return None;
+            }
+
+            Some(RemovableDevice {
+                name,
+                model: read("device/model").filter(|model| !model.is_empty()),
+                size_bytes: sectors * 512,
+                usb,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, Policy, fixture_root};
+
+    fn context(root: &str, allowed: bool) -> CheckContext {
+        CheckContext::new(FixtureRunner::new())
+            .with_root(fixture_root(root))
+            .with_policy(Policy {
+                removable_storage_allowed: allowed,
+                ..Policy::default()
+            })
+    }
+
+    #[test]
+    fn blacklisted_module_passes_when_forbidden() {
+        let outcome = check_linux(&context("linux/usb_blocked", false)).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.message,
+            "USB storage is blocked by a modprobe blacklist"
+        );
+        assert_eq!(
+            outcome.evidence[0].lines,
+            ["install usb-storage /bin/false"]
+        );
+    }
+
+    #[test]
+    fn attached_device_fails_when_forbidden() {
+        let outcome = check_linux(&context("linux/usb_attached", false)).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "USB storage is not blocked, but the policy forbids it"
+        );
+
+        let status: RemovableStorageStatus =
+            serde_json::from_value(*outcome.details.unwrap()).unwrap();
+        assert_eq!(
+            status.devices,
+            [RemovableDevice {
+                name: "sdb".to_string(),
+                model: Some("Cruzer Blade".to_string()),
+                size_bytes: 15_633_219_584,
+                usb: true,
+            }]
+        );
+        assert!(status.module_loaded);
+    }
+
+    #[test]
+    fn attached_device_passes_when_allowed() {
+        let outcome = check_linux(&context("linux/usb_attached", true)).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.message,
+            "Removable storage is allowed by policy, attached: sdb (Cruzer Blade)"
+        );
+    }
+
+    #[test]
+    fn usbguard_without_storage_rules_blocks() {
+        let outcome = check_linux(&context("linux/usb_usbguard", false)).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.message, "USB storage is blocked by USBGuard");
+    }
+}
@@ -0,0 +1,147 @@
+use super::{CheckOutcome, Evidence};
+use std::io;
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+pub struct CommandOutput {
+    pub command: String,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl CommandOutput {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    /// Evidence made of the given output lines.
+    pub fn evidence(&self, lines: &[String]) -> Evidence {
+        Evidence {
+            command: self.command.clone(),
+            exit_code: self.exit_code,
+            lines: lines.to_vec(),
+        }
+    }
+
+    /// Evidence made of every non-empty stdout line.
+    pub fn full_evidence(&self) -> Evidence {
+        self.evidence(&non_empty_lines(&self.stdout))
+    }
+
+    /// Evidence made of every non-empty stderr line.
+    pub fn stderr_evidence(&self) -> Evidence {
+        self.evidence(&non_empty_lines(&self.stderr))
+    }
+
+    /// Passes the output through if the command exited successfully.
+    pub fn ensure_success(self) -> Result<Self, CheckOutcome> {
+        if self.success() {
+            Ok(self)
+        } else {
+            Err(self.failure())
+        }
+    }
+
+    /// An error outcome for a command that exited unsuccessfully.
+    pub fn failure(&self) -> CheckOutcome {
+        let message = match self.exit_code {
+            Some(code) => format!("`{}` exited with status {}", self.command, code),
+            None => format!("`{}` was terminated by a signal", self.command),
+        };
+        CheckOutcome::error(message).with_evidence(self.stderr_evidence())
+    }
+}
+
+fn non_empty_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn command_line(program: &str, args: &[&str]) -> String {
+    std::iter::once(program)
+        .chain(args.iter().copied())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Executes external programs on behalf of the checks. Swapping the
+/// implementation lets tests replay recorded outputs instead of touching
+/// the host.
+pub trait CommandRunner: Send + Sync {
+    fn run(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput>;
+}
+
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput> {
+        let mut command = Command::new(program);
+        command.args(args);
+
+        #[cfg(target_os = "windows")]
+        command.creation_flags(0x08000000);
+
+        let output = command.output()?;
+        Ok(CommandOutput {
+            command: command_line(program, args),
+            exit_code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+}
+
+/// Replays recorded outputs keyed by the full command line. Commands that
+/// were not recorded behave as if the binary was not installed.
+#[cfg(test)]
+#[derive(Default)]
+pub struct FixtureRunner {
+    outputs: std::collections::HashMap<String, Result<(i32, String, String), io::ErrorKind>>,
+}
+
+#[cfg(test)]
+impl FixtureRunner {
+    pub fn new() -> Self {
+        FixtureRunner::default()
+    }
+
+    pub fn with_output(self, command: &str, stdout: &str) -> Self {
+        self.with_exit(command, 0, stdout, "")
+    }
+
+    pub fn with_exit(mut self, command: &str, exit_code: i32, stdout: &str, stderr: &str) -> Self {
+        self.outputs.insert(
+            command.to_string(),
+            Ok((exit_code, stdout.to_string(), stderr.to_string())),
+        );
+        self
+    }
+
+    pub fn with_error(mut self, command: &str, kind: io::ErrorKind) -> Self {
+        self.outputs.insert(command.to_string(), Err(kind));
+        self
+    }
+}
+
+#[cfg(test)]
+impl CommandRunner for FixtureRunner {
+    fn run(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput> {
+        let command = command_line(program, args);
+        match self.outputs.get(&command) {
+            Some(Ok((exit_code, stdout, stderr))) => Ok(CommandOutput {
+                command,
+                exit_code: Some(*exit_code),
+                stdout: stdout.clone(),
+                stderr: stderr.clone(),
+            }),
+            Some(Err(kind)) => Err(io::Error::from(*kind)),
+            None => Err(io::Error::from(io::ErrorKind::NotFound)),
+        }
+    }
+}
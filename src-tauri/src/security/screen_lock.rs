@@ -0,0 +1,1024 @@
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use super::runner::CommandOutput;
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+/// Longest idle time before locking that is still considered compliant.
+const MAX_LOCK_MINUTES: u32 = 15;
+
+pub struct ScreenLockCheck;
+
+#[async_trait]
+impl SecurityCheck for ScreenLockCheck {
+    fn id(&self) -> &'static str {
+        "screen_lock"
+    }
+
+    fn title(&self) -> &'static str {
+        "Screen Lock Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying screen lock is enabled"
+    }
+
+    fn category(&self) -> Category {
+        Category::Access
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        Platform::ALL
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "windows")]
+        let outcome = check_windows(ctx);
+        #[cfg(target_os = "macos")]
+        let outcome = check_macos(ctx);
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+fn locks_after(minutes: u32) -> CheckOutcome {
+    if minutes > MAX_LOCK_MINUTES {
+        CheckOutcome::warn(format!(
+            "Screen lock is enabled but only after {} minutes",
+            minutes
+        ))
+        .with_value(minutes.to_string())
+        .with_remediation(format!(
+            "Lock the screen after {} minutes of inactivity or less",
+            MAX_LOCK_MINUTES
+        ))
+    } else {
+        CheckOutcome::pass(format!("Screen lock is enabled: {} minutes", minutes))
+            .with_value(minutes.to_string())
+    }
+}
+
+fn lock_disabled(message: &str) -> CheckOutcome {
+    CheckOutcome::fail(message).with_remediation(format!(
+        "Require a password after {} minutes of inactivity or less",
+        MAX_LOCK_MINUTES
+    ))
+}
+
+/// "Turn off display after" timeouts in seconds, as read from
+/// `powercfg /q` output, which is localized with the Windows display
+/// language.
+#[cfg(any(target_os = "windows", test))]
+#[derive(Default)]
+struct DisplayTimeouts {
+    ac: Option<u32>,
+    dc: Option<u32>,
+    lines: Vec<String>,
+}
+
+#[cfg(any(target_os = "windows", test))]
+fn parse_powercfg(output: &str) -> DisplayTimeouts {
+    let mut timeouts = DisplayTimeouts::default();
+
+    for line in output.lines() {
+        let Some((label, value)) = line.rsplit_once(':') else {
+            continue;
+        };
+        let label = label.to_lowercase();
+        let value = value.trim();
+        let value = u32::from_str_radix(value.strip_prefix("0x").unwrap_or(value), 16).ok();
+
+        if label.contains("current ac power setting index")
+            || label.contains("corriente alterna actual")
+        {
+            timeouts.ac = value;
+        } else if label.contains("current dc power setting index")
+            || label.contains("corriente continua actual")
+        {
+            timeouts.dc = value;
+        } else {
+            continue;
+        }
+        timeouts.lines.push(line.trim().to_string());
+    }
+
+    timeouts
+}
+
+#[cfg(any(target_os = "windows", test))]
+fn check_windows(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let output = ctx
+        .run(
+            "powercfg",
+            &["/q", "SCHEME_CURRENT", "SUB_VIDEO", "VIDEOIDLE"],
+        )?
+        .ensure_success()?;
+
+    let timeouts = parse_powercfg(&output.stdout);
+    let evidence = output.evidence(&timeouts.lines);
+    let ac_timeout = timeouts.ac.ok_or_else(|| {
+        CheckOutcome::error("Could not parse the power settings reported by powercfg")
+            .with_evidence(output.full_evidence())
+    })?;
+
+    let battery = ctx.run(
+        "powershell",
+        &[
+            "-Command",
+            "[bool](Get-CimInstance -ClassName Win32_Battery -ErrorAction SilentlyContinue)",
+        ],
+    )?;
+    let has_battery = battery.stdout.trim().eq_ignore_ascii_case("true");
+
+    let timeout = match timeouts.dc {
+        Some(dc_timeout) if has_battery => std::cmp::max(ac_timeout, dc_timeout),
+        _ => ac_timeout,
+    };
+
+    if timeout == 0 {
+        Ok(lock_disabled("The display never turns off").with_evidence(evidence))
+    } else {
+        Ok(locks_after(timeout / 60).with_evidence(evidence))
+    }
+}
+
+#[cfg(any(target_os = "macos", test))]
+fn check_macos(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let output = ctx.run("sysadminctl", &["-screenLock", "status"])?;
+
+    // sysadminctl logs its answer to stderr
+    let result = format!("{}{}", output.stdout, output.stderr);
+    let status_evidence = output.evidence(&[result.trim().to_string()]);
+
+    if result.contains("screenLock is off") {
+        return Ok(lock_disabled("Screen lock is turned off").with_evidence(status_evidence));
+    }
+
+    let screensaver_output = ctx.run(
+        "defaults",
+        &["-currentHost", "read", "com.apple.screensaver", "idleTime"],
+    )?;
+
+    let screensaver_time = screensaver_output
+        .stdout
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| {
+            CheckOutcome::error("Could not read the screen saver idle time")
+                .with_evidence(screensaver_output.stderr_evidence())
+        })?
+        / 60;
+
+    let pmset = ctx.run("pmset", &["-g", "custom"])?;
+    let display_sleep_ac = parse_display_sleep(&pmset.stdout, "AC Power").ok_or_else(|| {
+        CheckOutcome::error("Could not read the display sleep time on AC Power")
+            .with_evidence(pmset.full_evidence())
+    })?;
+    // Desktop Macs have no battery section
+    let display_sleep_battery = parse_display_sleep(&pmset.stdout, "Battery Power").unwrap_or(0);
+
+    let max_timeout = std::cmp::max(
+        screensaver_time,
+        std::cmp::max(display_sleep_ac, display_sleep_battery),
+    );
+
+    if result.contains("screenLock delay is immediate") {
+        return Ok(locks_after(max_timeout).with_evidence(status_evidence));
+    }
+
+    if let Some(caps) = regex::Regex::new(r"screenLock delay is (\d+) seconds")
+        .ok()
+        .and_then(|re| re.captures(&result))
+    {
+        let screen_lock_delay = caps[1].parse::<u32>().unwrap_or_default() / 60;
+        return Ok(locks_after(max_timeout + screen_lock_delay).with_evidence(status_evidence));
+    }
+
+    Err(
+        CheckOutcome::error("Could not parse the screen lock status")
+            .with_evidence(status_evidence),
+    )
+}
+
+/// Reads `displaysleep` from the given section ("AC Power" or "Battery
+/// Power") of `pmset -g custom` output.
+#[cfg(any(target_os = "macos", test))]
+fn parse_display_sleep(output: &str, mode: &str) -> Option<u32> {
+    let mut in_section = false;
+
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            in_section = line.trim_end().strip_suffix(':') == Some(mode);
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        if in_section && fields.next() == Some("displaysleep") {
+            return fields.next().and_then(|time| time.parse::<u32>().ok());
+        }
+    }
+
+    None
+}
+
+/// Desktops with a screen lock backend of their own.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Desktop {
+    Gnome,
+    Kde,
+    Xfce,
+    Cinnamon,
+    Mate,
+    Sway,
+    Hyprland,
+}
+
+#[cfg(any(target_os = "linux", test))]
+impl Desktop {
+    fn from_name(name: &str) -> Option<Desktop> {
+        match name.to_lowercase().as_str() {
+            "gnome" | "gnome-classic" | "ubuntu" | "unity" | "pop" | "budgie" => {
+                Some(Desktop::Gnome)
+            }
+            "kde" | "plasma" | "plasmawayland" => Some(Desktop::Kde),
+            "xfce" | "xfce4" => Some(Desktop::Xfce),
+            "x-cinnamon" | "cinnamon" => Some(Desktop::Cinnamon),
+            "mate" => Some(Desktop::Mate),
+            "sway" => Some(Desktop::Sway),
+            "hyprland" => Some(Desktop::Hyprland),
+            _ => None,
+        }
+    }
+}
+
+/// The session's desktop, from the same variables desktop environments use
+/// to pick their autostart entries. `XDG_CURRENT_DESKTOP` may list several
+/// names, e.g. `ubuntu:GNOME`.
+#[cfg(any(target_os = "linux", test))]
+fn current_desktop(ctx: &CheckContext) -> Option<Result<Desktop, String>> {
+    let names: Vec<String> = [
+        "XDG_CURRENT_DESKTOP",
+        "XDG_SESSION_DESKTOP",
+        "DESKTOP_SESSION",
+    ]
+    .iter()
+    .filter_map(|variable| ctx.env(variable))
+    .flat_map(|value| value.split(':').map(str::to_string).collect::<Vec<_>>())
+    .collect();
+
+    let first = names.first()?.clone();
+    Some(
+        names
+            .iter()
+            .find_map(|name| Desktop::from_name(name))
+            .ok_or(first),
+    )
+}
+
+/// A file under the user's configuration directory.
+#[cfg(any(target_os = "linux", test))]
+fn config_path(ctx: &CheckContext, relative: &str) -> Option<String> {
+    let config_home = ctx
+        .env("XDG_CONFIG_HOME")
+        .or_else(|| ctx.env("HOME").map(|home| format!("{}/.config", home)))?;
+    Some(format!(
+        "{}/{}",
+        config_home.trim_end_matches('/'),
+        relative
+    ))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let desktop = current_desktop(ctx)
+        .ok_or_else(|| CheckOutcome::not_applicable("No desktop session was detected"))?;
+
+    match desktop {
+        Ok(Desktop::Gnome) => check_gsettings(ctx, &GNOME),
+        Ok(Desktop::Cinnamon) => check_gsettings(ctx, &CINNAMON),
+        Ok(Desktop::Mate) => check_gsettings(ctx, &MATE),
+        Ok(Desktop::Kde) => check_kde(ctx),
+        Ok(Desktop::Xfce) => check_xfce(ctx),
+        Ok(Desktop::Sway) => check_swayidle(ctx),
+        Ok(Desktop::Hyprland) => check_hypridle(ctx).or_else(|_| check_swayidle(ctx)),
+        // Window managers like awesome or i3 are often run on top of GNOME's settings
+        Err(name) => check_gsettings(ctx, &GNOME).map_err(|_| {
+            CheckOutcome::not_applicable(format!(
+                "Screen lock detection is not supported on {}",
+                name
+            ))
+        }),
+    }
+}
+
+/// Where a GNOME-derived desktop keeps its screen lock settings.
+#[cfg(any(target_os = "linux", test))]
+struct Schemas {
+    screensaver: &'static str,
+    session: &'static str,
+    /// Key that must be on for the screensaver to start when idle, if any.
+    activation: Option<&'static str>,
+    /// Whether the delays are stored in minutes rather than seconds.
+    minutes: bool,
+}
+
+#[cfg(any(target_os = "linux", test))]
+const GNOME: Schemas = Schemas {
+    screensaver: "org.gnome.desktop.screensaver",
+    session: "org.gnome.desktop.session",
+    activation: None,
+    minutes: false,
+};
+
+#[cfg(any(target_os = "linux", test))]
+const CINNAMON: Schemas = Schemas {
+    screensaver: "org.cinnamon.desktop.screensaver",
+    session: "org.cinnamon.desktop.session",
+    activation: None,
+    minutes: false,
+};
+
+#[cfg(any(target_os = "linux", test))]
+const MATE: Schemas = Schemas {
+    screensaver: "org.mate.screensaver",
+    session: "org.mate.session",
+    activation: Some("idle-activation-enabled"),
+    minutes: true,
+};
+
+#[cfg(any(target_os = "linux", test))]
+fn check_gsettings(ctx: &CheckContext, schemas: &Schemas) -> Result<CheckOutcome, CheckOutcome> {
+    let get = |schema: &str, key: &str| {
+        ctx.run("gsettings", &["get", schema, key])?
+            .ensure_success()
+    };
+
+    let lock_enabled = get(schemas.screensaver, "lock-enabled")?;
+    if lock_enabled.stdout.trim() != "true" {
+        return Ok(
+            lock_disabled("Screen lock is turned off").with_evidence(lock_enabled.full_evidence())
+        );
+    }
+
+    if let Some(key) = schemas.activation {
+        let activation = get(schemas.screensaver, key)?;
+        if activation.stdout.trim() != "true" {
+            return Ok(lock_disabled("The screensaver never starts when idle")
+                .with_evidence(activation.full_evidence()));
+        }
+    }
+
+    let idle_delay = get(schemas.session, "idle-delay")?;
+    let lock_delay = get(schemas.screensaver, "lock-delay")?;
+
+    // "uint32 300" for unsigned keys, a bare "5" for plain integers
+    let parse_delay = |output: &CommandOutput| {
+        output
+            .stdout
+            .split_whitespace()
+            .last()
+            .and_then(|s| s.parse::<u32>().ok())
+            .map(|delay| if schemas.minutes { delay * 60 } else { delay })
+            .ok_or_else(|| {
+                CheckOutcome::error("Could not parse the screen lock delays")
+                    .with_evidence(output.full_evidence())
+            })
+    };
+
+    let idle_seconds = parse_delay(&idle_delay)?;
+    let lock_seconds = parse_delay(&lock_delay)?;
+
+    if idle_seconds == 0 {
+        return Ok(
+            lock_disabled("The session never goes idle").with_evidence(idle_delay.full_evidence())
+        );
+    }
+
+    Ok(locks_after((idle_seconds + lock_seconds) / 60)
+        .with_evidence(idle_delay.full_evidence())
+        .with_evidence(lock_delay.full_evidence()))
+}
+
+/// Plasma's screen locker, configured in `kscreenlockerrc`. Keys left at
+/// their default are not written, so a missing file means locking after
+/// five minutes.
+#[cfg(any(target_os = "linux", test))]
+fn check_kde(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let path = config_path(ctx, "kscreenlockerrc")
+        .ok_or_else(|| CheckOutcome::error("Could not locate the KDE configuration directory"))?;
+    let config = ctx.read_file(&path).unwrap_or_default();
+
+    let settings: Vec<String> = ini_section(&config, "Daemon")
+        .filter(|line| line.starts_with("Autolock=") || line.starts_with("Timeout="))
+        .map(str::to_string)
+        .collect();
+    let value = |key: &str| {
+        settings
+            .iter()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+    };
+    let evidence = Evidence::file(&path, &settings);
+
+    if value("Autolock") == Some("false") {
+        return Ok(lock_disabled("Automatic screen locking is turned off").with_evidence(evidence));
+    }
+
+    let minutes = match value("Timeout") {
+        Some(timeout) => timeout.trim().parse::<u32>().map_err(|_| {
+            CheckOutcome::error("Could not parse the KDE screen lock timeout")
+                .with_evidence(evidence.clone())
+        })?,
+        None => 5,
+    };
+    if minutes == 0 {
+        return Ok(lock_disabled("The screen is never locked when idle").with_evidence(evidence));
+    }
+
+    Ok(locks_after(minutes).with_evidence(evidence))
+}
+
+/// Lines of an INI section, without comments or blank lines.
+#[cfg(any(target_os = "linux", test))]
+fn ini_section<'a>(config: &'a str, section: &'a str) -> impl Iterator<Item = &'a str> {
+    let header = format!("[{}]", section);
+    config
+        .lines()
+        .map(str::trim)
+        .skip_while(move |line| *line != header)
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// xfce4-screensaver, configured through xfconf. Properties left at their
+/// default do not exist in the channel.
+#[cfg(any(target_os = "linux", test))]
+fn check_xfce(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let mut evidence = Vec::new();
+    let mut query = |property: &str, default: &str| -> Result<String, CheckOutcome> {
+        let output = ctx.run("xfconf-query", &["-c", "xfce4-screensaver", "-p", property])?;
+        let value = if output.success() {
+            output.stdout.trim().to_string()
+        } else {
+            default.to_string()
+        };
+        evidence.push(output.evidence(&[format!("{} = {}", property, value)]));
+        Ok(value)
+    };
+
+    let saver_enabled = query("/saver/enabled", "true")?;
+    let lock_enabled = query("/lock/enabled", "true")?;
+    let idle_minutes = query("/saver/idle-activation/delay", "5")?;
+    let lock_minutes = query("/lock/saver-activation/delay", "0")?;
+
+    let outcome = if saver_enabled != "true" {
+        lock_disabled("The screensaver is turned off")
+    } else if lock_enabled != "true" {
+        lock_disabled("Screen lock is turned off")
+    } else {
+        match (idle_minutes.parse::<u32>(), lock_minutes.parse::<u32>()) {
+            (Ok(idle), Ok(lock)) => locks_after(idle + lock),
+            _ => CheckOutcome::error("Could not parse the XFCE screensaver delays"),
+        }
+    };
+
+    Ok(evidence
+        .into_iter()
+        .fold(outcome, |outcome, evidence| outcome.with_evidence(evidence)))
+}
+
+/// Whether an idle command locks the session.
+#[cfg(any(target_os = "linux", test))]
+fn is_lock_command(command: &str) -> bool {
+    ["swaylock", "hyprlock", "gtklock", "waylock", "lock-session"]
+        .iter()
+        .any(|locker| command.contains(locker))
+}
+
+/// The `timeout <seconds> <command>` rules of swayidle invocations, either
+/// in the sway config or in swayidle's own config file.
+#[cfg(any(target_os = "linux", test))]
+fn parse_swayidle_timeouts(config: &str, standalone: bool) -> Vec<(u32, String)> {
+    let timeout =
+        regex::Regex::new(r#"timeout\s+(\d+)\s+('[^']*'|"[^"]*"|\S+)"#).expect("valid regex");
+
+    config
+        .replace("\\\n", " ")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter(|line| standalone || line.contains("swayidle"))
+        .flat_map(|line| {
+            timeout
+                .captures_iter(line)
+                .filter_map(|caps| Some((caps[1].parse::<u32>().ok()?, caps[2].to_string())))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn check_swayidle(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let sources = [("sway/config", false), ("swayidle/config", true)];
+
+    let mut evidence = Vec::new();
+    let mut lock_timeouts = Vec::new();
+    for (relative, standalone) in sources {
+        let Some(path) = config_path(ctx, relative) else {
+            continue;
+        };
+        let Ok(config) = ctx.read_file(&path) else {
+            continue;
+        };
+
+        let timeouts = parse_swayidle_timeouts(&config, standalone);
+        let lines: Vec<String> = timeouts
+            .iter()
+            .map(|(seconds, command)| format!("timeout {} {}", seconds, command))
+            .collect();
+        evidence.push(Evidence::file(&path, &lines));
+        lock_timeouts.extend(
+            timeouts
+                .into_iter()
+                .filter(|(_, command)| is_lock_command(command))
+                .map(|(seconds, _)| seconds),
+        );
+    }
+
+    idle_lock_outcome("swayidle", lock_timeouts.into_iter().min(), evidence)
+}
+
+/// hypridle's `listener` blocks whose `on-timeout` locks the session.
+#[cfg(any(target_os = "linux", test))]
+fn parse_hypridle_lock_timeouts(config: &str) -> Vec<u32> {
+    let mut timeouts = Vec::new();
+    let mut listener: Option<(Option<u32>, bool)> = None;
+
+    for line in config.lines().map(str::trim) {
+        if line.starts_with("listener") {
+            listener = Some((None, false));
+            continue;
+        }
+        let Some((timeout, locks)) = listener.as_mut() else {
+            continue;
+        };
+
+        if line == "}" {
+            if let (Some(seconds), true) = (*timeout, *locks) {
+                timeouts.push(seconds);
+            }
+            listener = None;
+        } else if let Some((key, value)) = line.split_once('=') {
+            match key.trim() {
+                "timeout" => *timeout = value.trim().parse().ok(),
+                "on-timeout" => *locks = is_lock_command(value),
+                _ => {}
+            }
+        }
+    }
+
+    timeouts
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn check_hypridle(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let path = config_path(ctx, "hypr/hypridle.conf").ok_or_else(|| {
+        CheckOutcome::error("Could not locate the Hyprland configuration directory")
+    })?;
+    let config = ctx
+        .read_file(&path)
+        .map_err(|_| CheckOutcome::error(format!("Could not read {}", path)))?;
+
+    let timeouts = parse_hypridle_lock_timeouts(&config);
+    let lines: Vec<String> = timeouts
+        .iter()
+        .map(|seconds| format!("timeout = {} (locks)", seconds))
+        .collect();
+    let evidence = vec![Evidence::file(&path, &lines)];
+
+    idle_lock_outcome("hypridle", timeouts.into_iter().min(), evidence)
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn idle_lock_outcome(
+    daemon: &str,
+    seconds: Option<u32>,
+    evidence: Vec<Evidence>,
+) -> Result<CheckOutcome, CheckOutcome> {
+    let outcome = match seconds {
+        Some(seconds) => locks_after(seconds / 60),
+        None => lock_disabled(&format!("{} is not configured to lock the screen", daemon)),
+    };
+
+    Ok(evidence
+        .into_iter()
+        .fold(outcome, |outcome, evidence| outcome.with_evidence(evidence)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    const POWERCFG: &str = "powercfg /q SCHEME_CURRENT SUB_VIDEO VIDEOIDLE";
+    const BATTERY: &str = "powershell -Command [bool](Get-CimInstance -ClassName Win32_Battery -ErrorAction SilentlyContinue)";
+
+    #[test]
+    fn parses_english_powercfg() {
+        let timeouts = parse_powercfg(include_str!("fixtures/windows/powercfg_videoidle_en.txt"));
+        assert_eq!(timeouts.ac, Some(600));
+        assert_eq!(timeouts.dc, Some(300));
+        assert_eq!(timeouts.lines.len(), 2);
+    }
+
+    #[test]
+    fn parses_spanish_powercfg() {
+        let timeouts = parse_powercfg(include_str!("fixtures/windows/powercfg_videoidle_es.txt"));
+        assert_eq!(timeouts.ac, Some(900));
+        assert_eq!(timeouts.dc, Some(0));
+    }
+
+    #[test]
+    fn windows_uses_longest_timeout_on_battery_devices() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(
+                    POWERCFG,
+                    include_str!("fixtures/windows/powercfg_videoidle_en.txt"),
+                )
+                .with_output(BATTERY, "True\r\n"),
+        );
+
+        let outcome = check_windows(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("10"));
+    }
+
+    #[test]
+    fn windows_without_display_timeout_fails() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(POWERCFG, "Current AC Power Setting Index: 0x00000000\r\n")
+                .with_output(BATTERY, "False\r\n"),
+        );
+
+        assert_eq!(check_windows(&ctx).unwrap().status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn windows_unparseable_powercfg_is_an_error() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(POWERCFG, "garbage\r\n"));
+
+        assert_eq!(check_windows(&ctx).unwrap_err().status, CheckStatus::Error);
+    }
+
+    fn macos_runner(status: &str) -> FixtureRunner {
+        FixtureRunner::new()
+            .with_exit("sysadminctl -screenLock status", 0, "", status)
+            .with_output(
+                "defaults -currentHost read com.apple.screensaver idleTime",
+                "300\n",
+            )
+            .with_output(
+                "pmset -g custom",
+                include_str!("fixtures/macos/pmset_custom.txt"),
+            )
+    }
+
+    #[test]
+    fn parses_pmset_sections() {
+        let output = include_str!("fixtures/macos/pmset_custom.txt");
+        assert_eq!(parse_display_sleep(output, "AC Power"), Some(10));
+        assert_eq!(parse_display_sleep(output, "Battery Power"), Some(2));
+        assert_eq!(parse_display_sleep(output, "UPS Power"), None);
+    }
+
+    #[test]
+    fn macos_immediate_lock_uses_longest_idle_timeout() {
+        let ctx = CheckContext::new(macos_runner(
+            "2024-05-02 10:00:00.000 sysadminctl[1234:5678] screenLock delay is immediate\n",
+        ));
+
+        let outcome = check_macos(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("10"));
+    }
+
+    #[test]
+    fn macos_adds_lock_delay() {
+        let ctx = CheckContext::new(macos_runner(
+            "2024-05-02 10:00:00.000 sysadminctl[1234:5678] screenLock delay is 600 seconds\n",
+        ));
+
+        let outcome = check_macos(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Warn);
+        assert_eq!(outcome.value.as_deref(), Some("20"));
+    }
+
+    #[test]
+    fn macos_lock_off_fails() {
+        let ctx = CheckContext::new(macos_runner(
+            "2024-05-02 10:00:00.000 sysadminctl[1234:5678] screenLock is off\n",
+        ));
+
+        assert_eq!(check_macos(&ctx).unwrap().status, CheckStatus::Fail);
+    }
+
+    fn gsettings_runner(
+        screensaver: &str,
+        session: &str,
+        lock_enabled: &str,
+        idle_delay: &str,
+        lock_delay: &str,
+    ) -> FixtureRunner {
+        FixtureRunner::new()
+            .with_output(
+                &format!("gsettings get {} lock-enabled", screensaver),
+                lock_enabled,
+            )
+            .with_output(&format!("gsettings get {} idle-delay", session), idle_delay)
+            .with_output(
+                &format!("gsettings get {} lock-delay", screensaver),
+                lock_delay,
+            )
+    }
+
+    fn gnome(lock_enabled: &str, idle_delay: &str) -> CheckContext {
+        CheckContext::new(gsettings_runner(
+            "org.gnome.desktop.screensaver",
+            "org.gnome.desktop.session",
+            lock_enabled,
+            idle_delay,
+            "uint32 0\n",
+        ))
+        .with_env("XDG_CURRENT_DESKTOP", "ubuntu:GNOME")
+    }
+
+    fn home(name: &str) -> CheckContext {
+        CheckContext::new(FixtureRunner::new())
+            .with_root(fixture_root(name))
+            .with_env("HOME", "/home/alice")
+    }
+
+    #[test]
+    fn detects_desktop_from_session_variables() {
+        let ctx = CheckContext::new(FixtureRunner::new());
+        assert_eq!(
+            current_desktop(&ctx.clone().with_env("XDG_CURRENT_DESKTOP", "ubuntu:GNOME")),
+            Some(Ok(Desktop::Gnome))
+        );
+        assert_eq!(
+            current_desktop(&ctx.clone().with_env("XDG_CURRENT_DESKTOP", "X-Cinnamon")),
+            Some(Ok(Desktop::Cinnamon))
+        );
+        assert_eq!(
+            current_desktop(&ctx.clone().with_env("XDG_SESSION_DESKTOP", "KDE")),
+            Some(Ok(Desktop::Kde))
+        );
+        assert_eq!(
+            current_desktop(&ctx.clone().with_env("DESKTOP_SESSION", "i3")),
+            Some(Err("i3".to_string()))
+        );
+        assert_eq!(current_desktop(&ctx.with_env("HOME", "/home/alice")), None);
+    }
+
+    #[test]
+    fn linux_gnome_lock_after_idle_delay() {
+        let outcome = check_linux(&gnome("true\n", "uint32 300\n")).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("5"));
+    }
+
+    #[test]
+    fn linux_gnome_long_idle_delay_warns() {
+        assert_eq!(
+            check_linux(&gnome("true\n", "uint32 1800\n"))
+                .unwrap()
+                .status,
+            CheckStatus::Warn
+        );
+    }
+
+    #[test]
+    fn linux_gnome_lock_disabled_fails() {
+        assert_eq!(
+            check_linux(&gnome("false\n", "uint32 300\n"))
+                .unwrap()
+                .status,
+            CheckStatus::Fail
+        );
+    }
+
+    #[test]
+    fn linux_cinnamon_uses_its_own_schemas() {
+        let ctx = CheckContext::new(gsettings_runner(
+            "org.cinnamon.desktop.screensaver",
+            "org.cinnamon.desktop.session",
+            "true\n",
+            "uint32 600\n",
+            "uint32 120\n",
+        ))
+        .with_env("XDG_CURRENT_DESKTOP", "X-Cinnamon");
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("12"));
+    }
+
+    #[test]
+    fn linux_mate_delays_are_in_minutes() {
+        let runner = gsettings_runner(
+            "org.mate.screensaver",
+            "org.mate.session",
+            "true\n",
+            "10\n",
+            "2\n",
+        )
+        .with_output(
+            "gsettings get org.mate.screensaver idle-activation-enabled",
+            "true\n",
+        );
+        let ctx = CheckContext::new(runner).with_env("XDG_CURRENT_DESKTOP", "MATE");
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("12"));
+    }
+
+    #[test]
+    fn linux_mate_without_idle_activation_fails() {
+        let runner = gsettings_runner(
+            "org.mate.screensaver",
+            "org.mate.session",
+            "true\n",
+            "10\n",
+            "2\n",
+        )
+        .with_output(
+            "gsettings get org.mate.screensaver idle-activation-enabled",
+            "false\n",
+        );
+        let ctx = CheckContext::new(runner).with_env("XDG_CURRENT_DESKTOP", "MATE");
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(outcome.message, "The screensaver never starts when idle");
+    }
+
+    #[test]
+    fn linux_kde_reads_kscreenlockerrc() {
+        let ctx = home("linux/kde").with_env("XDG_CURRENT_DESKTOP", "KDE");
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Warn);
+        assert_eq!(outcome.value.as_deref(), Some("30"));
+        assert_eq!(
+            outcome.evidence[0].command,
+            "/home/alice/.config/kscreenlockerrc"
+        );
+        assert_eq!(outcome.evidence[0].lines, ["Autolock=true", "Timeout=30"]);
+    }
+
+    #[test]
+    fn linux_kde_defaults_without_config() {
+        let ctx = home("linux/empty").with_env("XDG_CURRENT_DESKTOP", "KDE");
+
+        let outcome = check_kde(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("5"));
+    }
+
+    #[test]
+    fn linux_xfce_falls_back_to_defaults() {
+        const MISSING: &str =
+            "Property \"/lock/enabled\" does not exist on channel \"xfce4-screensaver\".\n";
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(
+                    "xfconf-query -c xfce4-screensaver -p /saver/enabled",
+                    "true\n",
+                )
+                .with_exit(
+                    "xfconf-query -c xfce4-screensaver -p /lock/enabled",
+                    1,
+                    "",
+                    MISSING,
+                )
+                .with_output(
+                    "xfconf-query -c xfce4-screensaver -p /saver/idle-activation/delay",
+                    "10\n",
+                )
+                .with_exit(
+                    "xfconf-query -c xfce4-screensaver -p /lock/saver-activation/delay",
+                    1,
+                    "",
+                    MISSING,
+                ),
+        )
+        .with_env("XDG_CURRENT_DESKTOP", "XFCE");
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("10"));
+        assert_eq!(outcome.evidence[1].lines, ["/lock/enabled = true"]);
+    }
+
+    #[test]
+    fn linux_xfce_lock_disabled_fails() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(
+                    "xfconf-query -c xfce4-screensaver -p /saver/enabled",
+                    "true\n",
+                )
+                .with_output(
+                    "xfconf-query -c xfce4-screensaver -p /lock/enabled",
+                    "false\n",
+                )
+                .with_output(
+                    "xfconf-query -c xfce4-screensaver -p /saver/idle-activation/delay",
+                    "5\n",
+                )
+                .with_output(
+                    "xfconf-query -c xfce4-screensaver -p /lock/saver-activation/delay",
+                    "0\n",
+                ),
+        );
+
+        assert_eq!(check_xfce(&ctx).unwrap().status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn parses_swayidle_invocations() {
+        let timeouts = parse_swayidle_timeouts(
+            include_str!("fixtures/linux/sway/home/alice/.config/sway/config"),
+            false,
+        );
+
+        assert_eq!(
+            timeouts,
+            [
+                (300, "'swaylock -f -c 000000'".to_string()),
+                (600, "'swaymsg \"output * power off\"'".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn linux_sway_locks_after_swayidle_timeout() {
+        let ctx = home("linux/sway").with_env("XDG_CURRENT_DESKTOP", "sway");
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("5"));
+    }
+
+    #[test]
+    fn linux_sway_without_swayidle_fails() {
+        let ctx = home("linux/empty").with_env("XDG_CURRENT_DESKTOP", "sway");
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "swayidle is not configured to lock the screen"
+        );
+    }
+
+    #[test]
+    fn linux_hyprland_reads_hypridle_listeners() {
+        let ctx = home("linux/hyprland").with_env("XDG_CURRENT_DESKTOP", "Hyprland");
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("10"));
+        assert_eq!(outcome.evidence[0].lines, ["timeout = 600 (locks)"]);
+    }
+
+    #[test]
+    fn linux_unknown_window_manager_is_not_applicable() {
+        let ctx = CheckContext::new(FixtureRunner::new()).with_env("XDG_CURRENT_DESKTOP", "i3");
+
+        let outcome = check_linux(&ctx).unwrap_err();
+        assert_eq!(outcome.status, CheckStatus::NotApplicable);
+        assert_eq!(
+            outcome.message,
+            "Screen lock detection is not supported on i3"
+        );
+    }
+
+    #[test]
+    fn linux_without_desktop_session_is_not_applicable() {
+        let ctx = CheckContext::new(FixtureRunner::new()).with_env("HOME", "/home/alice");
+
+        assert_eq!(
+            check_linux(&ctx).unwrap_err().status,
+            CheckStatus::NotApplicable
+        );
+    }
+}
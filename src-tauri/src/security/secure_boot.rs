@@ -0,0 +1,207 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use serde::{Deserialize, Serialize};
+
+pub struct SecureBootCheck;
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SecureBootStatus {
+    /// `enabled`, `disabled` or `unsupported` on legacy BIOS.
+    pub state: String,
+    /// Kernel lockdown mode: `none`, `integrity` or `confidentiality`.
+    pub lockdown: Option<String>,
+}
+
+#[async_trait]
+impl SecurityCheck for SecureBootCheck {
+    fn id(&self) -> &'static str {
+        "secure_boot"
+    }
+
+    fn title(&self) -> &'static str {
+        "Secure Boot Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying Secure Boot is enabled"
+    }
+
+    fn category(&self) -> Category {
+        Category::Access
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Secure Boot detection is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+const EFIVARS: &str = "/sys/firmware/efi/efivars";
+#[cfg(any(target_os = "linux", test))]
+const LOCKDOWN: &str = "/sys/kernel/security/lockdown";
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let mut evidence = Vec::new();
+
+    let lockdown = ctx.read_file(LOCKDOWN).ok().and_then(|modes| {
+        evidence.push(Evidence::file(LOCKDOWN, &[modes.trim().to_string()]));
+        parse_lockdown(&modes)
+    });
+
+    // The kernel only exposes /sys/firmware/efi when booted through UEFI
+    if !ctx.path("/sys/firmware/efi").exists() {
+        let status = SecureBootStatus {
+            state: "unsupported".to_string(),
+            lockdown,
+        };
+        let outcome = CheckOutcome::fail(
+            "The system boots in legacy BIOS mode, which does not support Secure Boot",
+        )
+        .with_value(status.state.as_str())
+        .with_evidence(Evidence::file("/sys/firmware/efi", &[]))
+        .with_remediation("Reinstall in UEFI mode and enable Secure Boot in the firmware settings");
+        return Ok(evidence
+            .into_iter()
+            .fold(outcome, CheckOutcome::with_evidence)
+            .with_details(&status));
+    }
+
+    let variable = std::fs::read_dir(ctx.path(EFIVARS))
+        .ok()
+        .into_iter()
+        .flatten()
+        .flatten()
+        .find(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with("SecureBoot-")
+        })
+        .ok_or_else(|| {
+            CheckOutcome::error(format!(
+                "The SecureBoot variable was not found in {}; efivarfs may not be mounted",
+                EFIVARS
+            ))
+        })?;
+    let contents = std::fs::read(variable.path()).map_err(|e| {
+        CheckOutcome::error(format!("Could not read the SecureBoot variable: {}", e))
+    })?;
+
+    // efivarfs prefixes the value with four bytes of attributes
+    let enabled = match contents.get(4) {
+        Some(1) => true,
+        Some(0) => false,
+        _ => {
+            return Err(CheckOutcome::error(
+                "The SecureBoot variable has an unexpected format",
+            ));
+        }
+    };
+    evidence.insert(
+        0,
+        Evidence::file(
+            &format!("{}/{}", EFIVARS, variable.file_name().to_string_lossy()),
+            &[format!("{:02x?}", contents)],
+        ),
+    );
+
+    let status = SecureBootStatus {
+        state: if enabled { "enabled" } else { "disabled" }.to_string(),
+        lockdown,
+    };
+    let outcome = if enabled {
+        CheckOutcome::pass(format!(
+            "Secure Boot is enabled, kernel lockdown: {}",
+            status.lockdown.as_deref().unwrap_or("unavailable")
+        ))
+    } else {
+        CheckOutcome::fail("Secure Boot is disabled")
+            .with_remediation("Enable Secure Boot in the UEFI firmware settings")
+    };
+
+    Ok(evidence
+        .into_iter()
+        .fold(
+            outcome.with_value(status.state.as_str()),
+            CheckOutcome::with_evidence,
+        )
+        .with_details(&status))
+}
+
+/// The active mode, which the kernel lists in brackets, e.g.
+/// `none [integrity] confidentiality`.
+#[cfg(any(target_os = "linux", test))]
+fn parse_lockdown(modes: &str) -> Option<String> {
+    modes
+        .split_whitespace()
+        .find_map(|mode| mode.strip_prefix('[')?.strip_suffix(']'))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    fn context(root: &str) -> CheckContext {
+        CheckContext::new(FixtureRunner::new()).with_root(fixture_root(root))
+    }
+
+    #[test]
+    fn enabled_secure_boot_passes_with_lockdown() {
+        let outcome = check_linux(&context("linux/secure_boot_enabled")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.message,
+            "Secure Boot is enabled, kernel lockdown: integrity"
+        );
+        assert_eq!(outcome.evidence[0].lines, ["[06, 00, 00, 00, 01]"]);
+    }
+
+    #[test]
+    fn disabled_secure_boot_fails() {
+        let outcome = check_linux(&context("linux/secure_boot_disabled")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(outcome.value.as_deref(), Some("disabled"));
+    }
+
+    #[test]
+    fn legacy_bios_is_unsupported() {
+        let outcome = check_linux(&context("linux/empty")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(outcome.value.as_deref(), Some("unsupported"));
+    }
+
+    #[test]
+    fn parses_active_lockdown_mode() {
+        assert_eq!(
+            parse_lockdown("[none] integrity confidentiality\n").as_deref(),
+            Some("none")
+        );
+        assert_eq!(parse_lockdown(""), None);
+    }
+}
@@ -0,0 +1,505 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use super::firewall::service_active;
+#[cfg(any(target_os = "linux", test))]
+use super::listening_ports::listening_tcp_ports;
+#[cfg(any(target_os = "linux", test))]
+use serde::{Deserialize, Serialize};
+#[cfg(any(target_os = "linux", test))]
+use std::io::ErrorKind;
+
+pub struct SshdCheck;
+
+/// The effective settings of the OpenSSH server.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SshdStatus {
+    pub running: bool,
+    pub permit_root_login: String,
+    pub password_authentication: String,
+    pub listen_addresses: Vec<String>,
+    /// `Match` blocks that loosen the global settings, e.g.
+    /// `Match User deploy: PasswordAuthentication yes`.
+    pub match_overrides: Vec<String>,
+}
+
+#[async_trait]
+impl SecurityCheck for SshdCheck {
+    fn id(&self) -> &'static str {
+        "ssh_server"
+    }
+
+    fn title(&self) -> &'static str {
+        "SSH Server Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying the SSH server only accepts key-based logins"
+    }
+
+    fn category(&self) -> Category {
+        Category::Access
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "SSH server detection is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+const SSHD_CONFIG: &str = "/etc/ssh/sshd_config";
+
+/// A directive from `sshd_config` with its includes expanded, and the
+/// `Match` block it belongs to, if any.
+#[cfg(any(target_os = "linux", test))]
+struct Directive {
+    keyword: String,
+    value: String,
+    condition: Option<String>,
+    /// `file:line`, for the evidence.
+    source: String,
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let process = ctx.processes().iter().any(|name| name == "sshd");
+    // With socket activation sshd only runs while a connection is open
+    let socket = ["ssh.socket", "sshd.socket"]
+        .iter()
+        .find_map(|unit| service_active(ctx, unit));
+    if !ctx.path(SSHD_CONFIG).exists() && !process && socket.is_none() {
+        return Ok(
+            CheckOutcome::pass("The SSH server is not installed").with_value("not installed")
+        );
+    }
+
+    let mut directives = Vec::new();
+    let mut unreadable = Vec::new();
+    let mut condition = None;
+    read_config(
+        ctx,
+        SSHD_CONFIG,
+        &mut condition,
+        &mut directives,
+        &mut unreadable,
+        0,
+    )
+    .map_err(|e| CheckOutcome::error(format!("Could not read {}: {}", SSHD_CONFIG, e)))?;
+
+    // sshd uses the first value it reads for most keywords
+    let global = |keyword: &str| {
+        directives
+            .iter()
+            .find(|directive| directive.condition.is_none() && directive.keyword == keyword)
+    };
+    let setting = |keyword: &str, default: &str| {
+        global(keyword).map_or_else(
+            || default.to_string(),
+            |directive| directive.value.to_lowercase(),
+        )
+    };
+    let permit_root_login = setting("permitrootlogin", "prohibit-password");
+    let password_authentication = setting("passwordauthentication", "yes");
+
+    let ports: Vec<&str> = directives
+        .iter()
+        .filter(|directive| directive.condition.is_none() && directive.keyword == "port")
+        .map(|directive| directive.value.as_str())
+        .collect();
+    let ports = if ports.is_empty() { vec!["22"] } else { ports };
+    let mut addresses: Vec<&str> = directives
+        .iter()
+        .filter(|directive| directive.condition.is_none() && directive.keyword == "listenaddress")
+        .map(|directive| directive.value.as_str())
+        .collect();
+    if addresses.is_empty() {
+        addresses = vec!["0.0.0.0", "[::]"];
+    }
+    let listen_addresses: Vec<String> = addresses
+        .iter()
+        .flat_map(|address| listen_address(address, &ports))
+        .collect();
+
+    // sshd may run under another name, e.g. in a container, but something
+    // listening on its port is still an SSH server
+    let listening = listening_tcp_ports(ctx);
+    let running = process
+        || socket.is_some()
+        || listen_addresses.iter().any(|address| {
+            address
+                .rsplit_once(':')
+                .and_then(|(_, port)| port.parse::<u16>().ok())
+                .is_some_and(|port| listening.contains(&port))
+        });
+
+    let overrides: Vec<(&str, String)> = directives
+        .iter()
+        .filter(|directive| is_loosening(&directive.keyword, &directive.value))
+        .filter_map(|directive| {
+            let setting = format!("{} {}", directive.keyword_name(), directive.value);
+            Some((directive.condition.as_deref()?, setting))
+        })
+        .collect();
+
+    let mut findings = Vec::new();
+    if permit_root_login == "yes" {
+        findings.push("root login is permitted".to_string());
+    }
+    if password_authentication == "yes" {
+        findings.push("password authentication is enabled".to_string());
+    }
+    findings.extend(
+        overrides
+            .iter()
+            .map(|(condition, setting)| format!("{} in `{}`", setting, condition)),
+    );
+
+    let evidence: Vec<String> = directives
+        .iter()
+        .filter(|directive| {
+            matches!(
+                directive.keyword.as_str(),
+                "permitrootlogin" | "passwordauthentication" | "port" | "listenaddress"
+            )
+        })
+        .map(|directive| {
+            format!(
+                "{}: {} {}",
+                directive.source,
+                directive.keyword_name(),
+                directive.value
+            )
+        })
+        .collect();
+
+    let status = SshdStatus {
+        running,
+        permit_root_login,
+        password_authentication,
+        listen_addresses,
+        match_overrides: overrides
+            .iter()
+            .map(|(condition, setting)| format!("{}: {}", condition, setting))
+            .collect(),
+    };
+    let outcome = if !running {
+        CheckOutcome::pass("The SSH server is installed but not running").with_value("not running")
+    } else if findings.is_empty() && !unreadable.is_empty() {
+        CheckOutcome::warn(format!(
+            "The SSH server seems to only accept key-based logins, but {} could not be read without root",
+            unreadable.join(", ")
+        ))
+        .with_value("running")
+    } else if findings.is_empty() {
+        CheckOutcome::pass("The SSH server only accepts key-based logins").with_value("running")
+    } else {
+        CheckOutcome::fail(format!("The SSH server is running and {}", findings.join(", ")))
+            .with_value("running")
+            .with_remediation(
+                "Set PasswordAuthentication no and PermitRootLogin prohibit-password in /etc/ssh/sshd_config.d, then restart sshd",
+            )
+    };
+
+    Ok(socket
+        .into_iter()
+        .fold(
+            outcome.with_evidence(Evidence::file(SSHD_CONFIG, &evidence)),
+            CheckOutcome::with_evidence,
+        )
+        .with_details(&status))
+}
+
+#[cfg(any(target_os = "linux", test))]
+impl Directive {
+    fn keyword_name(&self) -> &'static str {
+        match self.keyword.as_str() {
+            "permitrootlogin" => "PermitRootLogin",
+            "passwordauthentication" => "PasswordAuthentication",
+            "port" => "Port",
+            "listenaddress" => "ListenAddress",
+            _ => "",
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn is_loosening(keyword: &str, value: &str) -> bool {
+    matches!(keyword, "permitrootlogin" | "passwordauthentication")
+        && value.eq_ignore_ascii_case("yes")
+}
+
+/// Reads a config file, expanding `Include` directives in place. Includes
+/// inside a `Match` block stay part of it, and included files we may not
+/// read are collected in `unreadable`.
+#[cfg(any(target_os = "linux", test))]
+fn read_config(
+    ctx: &CheckContext,
+    path: &str,
+    condition: &mut Option<String>,
+    directives: &mut Vec<Directive>,
+    unreadable: &mut Vec<String>,
+    depth: usize,
+) -> std::io::Result<()> {
+    let contents = ctx.read_file(path)?;
+
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (keyword, value) = line
+            .split_once(|c: char| c.is_whitespace() || c == '=')
+            .map_or((line, ""), |(keyword, value)| {
+                (
+                    keyword,
+                    value.trim_start_matches(|c: char| c.is_whitespace() || c == '='),
+                )
+            });
+        let keyword = keyword.to_lowercase();
+        let value = value.trim().trim_matches('"').to_string();
+
+        match keyword.as_str() {
+            "match" => {
+                *condition =
+                    (!value.eq_ignore_ascii_case("all")).then(|| format!("Match {}", value));
+            }
+            // sshd limits include depth the same way
+            "include" if depth < 16 => {
+                for pattern in value.split_whitespace() {
+                    for include in expand_include(ctx, pattern) {
+                        // A `Match` opened in an included file ends with it
+                        let outer = condition.clone();
+                        let result = read_config(
+                            ctx,
+                            &include,
+                            condition,
+                            directives,
+                            unreadable,
+                            depth + 1,
+                        );
+                        *condition = outer;
+                        // sshd itself skips includes that do not exist
+                        if result.is_err_and(|e| e.kind() == ErrorKind::PermissionDenied) {
+                            unreadable.push(include);
+                        }
+                    }
+                }
+            }
+            _ => directives.push(Directive {
+                keyword,
+                value,
+                condition: condition.clone(),
+                source: format!("{}:{}", path, number + 1),
+            }),
+        }
+    }
+    Ok(())
+}
+
+/// The files an `Include` pattern names, sorted as sshd reads them.
+/// Relative paths are relative to `/etc/ssh`.
+#[cfg(any(target_os = "linux", test))]
+fn expand_include(ctx: &CheckContext, pattern: &str) -> Vec<String> {
+    let pattern = if pattern.starts_with('/') {
+        pattern.to_string()
+    } else {
+        format!("/etc/ssh/{}", pattern)
+    };
+    let Some((directory, name)) = pattern.rsplit_once('/') else {
+        return Vec::new();
+    };
+    if !name.contains(['*', '?']) {
+        return vec![pattern];
+    }
+
+    let mut files: Vec<String> = std::fs::read_dir(ctx.path(directory))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|file| wildcard_match(name, file))
+        .map(|file| format!("{}/{}", directory, file))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Matches a file name against a pattern with `*` and `?` wildcards.
+#[cfg(any(target_os = "linux", test))]
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let mut pattern_chars = pattern.chars();
+    match pattern_chars.next() {
+        None => name.is_empty(),
+        Some('*') => {
+            let rest = pattern_chars.as_str();
+            name.char_indices()
+                .map(|(index, _)| index)
+                .chain([name.len()])
+                .any(|index| wildcard_match(rest, &name[index..]))
+        }
+        Some(expected) => {
+            let mut name_chars = name.chars();
+            match name_chars.next() {
+                Some(c) if expected == '?' || expected == c => {
+                    wildcard_match(pattern_chars.as_str(), name_chars.as_str())
+                }
+                _ => false,
+            }
+        }
+    }
+}
+
+/// `ListenAddress` values with the ports they listen on. An address can
+/// carry its own port, as in `10.0.0.1:2222` or `[::1]:2222`.
+#[cfg(any(target_os = "linux", test))]
+fn listen_address(address: &str, ports: &[&str]) -> Vec<String> {
+    let has_port = if address.starts_with('[') {
+        address.contains("]:")
+    } else {
+        address.matches(':').count() == 1
+    };
+    if has_port {
+        return vec![address.to_string()];
+    }
+
+    let host = if address.contains(':') && !address.starts_with('[') {
+        format!("[{}]", address)
+    } else {
+        address.to_string()
+    };
+    ports
+        .iter()
+        .map(|port| format!("{}:{}", host, port))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    fn check(root: &str) -> (CheckOutcome, Option<SshdStatus>) {
+        check_with(FixtureRunner::new(), root)
+    }
+
+    fn check_with(runner: FixtureRunner, root: &str) -> (CheckOutcome, Option<SshdStatus>) {
+        let ctx = CheckContext::new(runner).with_root(fixture_root(root));
+        let outcome = check_linux(&ctx).unwrap();
+        let status = outcome
+            .details
+            .clone()
+            .map(|details| serde_json::from_value(*details).unwrap());
+        (outcome, status)
+    }
+
+    #[test]
+    fn first_include_wins_over_the_main_config() {
+        let (outcome, status) = check("linux/sshd_password");
+        let status = status.unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(status.password_authentication, "yes");
+        assert_eq!(status.permit_root_login, "prohibit-password");
+        assert_eq!(status.listen_addresses, ["0.0.0.0:22", "[::]:22"]);
+        assert!(
+            outcome.evidence[0].lines[0].starts_with("/etc/ssh/sshd_config.d/50-cloud-init.conf:1")
+        );
+    }
+
+    #[test]
+    fn match_blocks_do_not_change_global_settings() {
+        let (outcome, status) = check("linux/sshd_hardened");
+        let status = status.unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(status.password_authentication, "no");
+        assert_eq!(status.permit_root_login, "no");
+        assert_eq!(status.listen_addresses, ["127.0.0.1:2222", "[::1]:2222"]);
+        assert_eq!(
+            status.match_overrides,
+            ["Match Address 10.0.0.0/8: PasswordAuthentication yes"]
+        );
+        assert_eq!(
+            outcome.message,
+            "The SSH server is running and PasswordAuthentication yes in `Match Address 10.0.0.0/8`"
+        );
+    }
+
+    #[test]
+    fn match_in_an_include_ends_with_the_file() {
+        let (outcome, status) = check("linux/sshd_match_include");
+        let status = status.unwrap();
+
+        assert_eq!(status.permit_root_login, "yes");
+        assert_eq!(status.password_authentication, "no");
+        assert!(status.match_overrides.is_empty());
+        assert_eq!(
+            outcome.message,
+            "The SSH server is running and root login is permitted"
+        );
+    }
+
+    #[test]
+    fn socket_activated_sshd_is_running() {
+        let (stopped, _) = check("linux/sshd_socket");
+        assert_eq!(stopped.value.as_deref(), Some("not running"));
+
+        let (outcome, status) = check_with(
+            FixtureRunner::new().with_output("systemctl is-active ssh.socket", "active\n"),
+            "linux/sshd_socket",
+        );
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert!(status.unwrap().running);
+        assert_eq!(
+            outcome.message,
+            "The SSH server is running and password authentication is enabled"
+        );
+        assert_eq!(
+            outcome.evidence[1].command,
+            "systemctl is-active ssh.socket"
+        );
+    }
+
+    #[test]
+    fn listener_on_the_configured_port_is_running() {
+        let (outcome, status) = check("linux/sshd_listener");
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("running"));
+        assert!(status.unwrap().running);
+    }
+
+    #[test]
+    fn missing_sshd_passes() {
+        let (outcome, _) = check("linux/empty");
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("not installed"));
+    }
+
+    #[test]
+    fn matches_include_wildcards() {
+        assert!(wildcard_match("*.conf", "50-cloud-init.conf"));
+        assert!(wildcard_match("?0-*.conf", "50-cloud-init.conf"));
+        assert!(!wildcard_match("*.conf", "50-cloud-init.conf.bak"));
+    }
+}
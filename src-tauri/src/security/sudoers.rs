@@ -0,0 +1,622 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use serde::{Deserialize, Serialize};
+#[cfg(any(target_os = "linux", test))]
+use std::collections::HashMap;
+#[cfg(any(target_os = "linux", test))]
+use std::io::{self, ErrorKind};
+
+pub struct SudoersCheck;
+
+/// Who can run commands as another user through sudo.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct SudoStatus {
+    pub rules: Vec<SudoRule>,
+    /// Users and `%groups` granted at least one rule, aliases expanded.
+    pub principals: Vec<String>,
+    pub current_user: Option<String>,
+    /// Administrative groups (`sudo`, `wheel`, `admin`) the current user
+    /// belongs to.
+    pub admin_groups: Vec<String>,
+    pub sudoers_readable: bool,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SudoRule {
+    pub principals: Vec<String>,
+    pub commands: Vec<SudoCommand>,
+    /// `file:line` of the rule.
+    pub source: String,
+    pub line: String,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SudoCommand {
+    pub command: String,
+    pub runas: Option<String>,
+    pub nopasswd: bool,
+}
+
+#[async_trait]
+impl SecurityCheck for SudoersCheck {
+    fn id(&self) -> &'static str {
+        "sudo_privileges"
+    }
+
+    fn title(&self) -> &'static str {
+        "Sudo Privileges Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying sudo always asks for a password"
+    }
+
+    fn category(&self) -> Category {
+        Category::Access
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Sudo auditing is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+const SUDOERS: &str = "/etc/sudoers";
+#[cfg(any(target_os = "linux", test))]
+const ADMIN_GROUPS: &[&str] = &["sudo", "wheel", "admin"];
+
+/// Nesting limit for includes, as sudo itself enforces.
+#[cfg(any(target_os = "linux", test))]
+const MAX_INCLUDE_DEPTH: usize = 128;
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let mut status = SudoStatus {
+        current_user: ctx.env("USER"),
+        ..SudoStatus::default()
+    };
+    if let Some(user) = &status.current_user {
+        status.admin_groups = admin_groups(ctx, user);
+    }
+
+    let mut sudoers = Sudoers::default();
+    match sudoers.read(ctx, SUDOERS, 0) {
+        Ok(()) => status.sudoers_readable = true,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            return Ok(CheckOutcome::pass("sudo is not installed")
+                .with_value("not installed")
+                .with_details(&status));
+        }
+        // /etc/sudoers is only readable by root
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => {}
+        Err(e) => {
+            return Err(CheckOutcome::error(format!(
+                "Could not read {}: {}",
+                SUDOERS, e
+            )));
+        }
+    }
+    status.rules = sudoers.rules();
+    for principal in status.rules.iter().flat_map(|rule| &rule.principals) {
+        if !status.principals.contains(principal) {
+            status.principals.push(principal.clone());
+        }
+    }
+
+    let nopasswd: Vec<&SudoRule> = status
+        .rules
+        .iter()
+        .filter(|rule| rule.commands.iter().any(|command| command.nopasswd))
+        .collect();
+    let everyone: Vec<&SudoRule> = status
+        .rules
+        .iter()
+        .filter(|rule| {
+            rule.principals
+                .iter()
+                .any(|principal| principal == "ALL" || principal == "%users")
+        })
+        .collect();
+
+    let mut passwordless = Vec::new();
+    if sudoers.authenticate_disabled {
+        passwordless.push("`Defaults !authenticate` disables passwords for every rule".to_string());
+    }
+    if !nopasswd.is_empty() {
+        passwordless.push(format!(
+            "NOPASSWD rules for {}",
+            names(nopasswd.iter().flat_map(|rule| &rule.principals))
+        ));
+    }
+
+    let mut findings = Vec::new();
+    let mut remediations = Vec::new();
+    if !passwordless.is_empty() {
+        findings.push(format!(
+            "sudo grants root without a password check: {}",
+            passwordless.join(", ")
+        ));
+        remediations.push(
+            "Remove NOPASSWD tags and `Defaults !authenticate` from /etc/sudoers and /etc/sudoers.d with `visudo`",
+        );
+    }
+    if !everyone.is_empty() {
+        findings.push("sudo grants root to every user".to_string());
+        remediations.push(
+            "Limit sudo rules to an administrative group such as sudo or wheel with `visudo`",
+        );
+    }
+
+    let evidence: Vec<String> = nopasswd
+        .iter()
+        .chain(&everyone)
+        .map(|rule| format!("{}: {}", rule.source, rule.line))
+        .collect();
+    let is_admin = !status.admin_groups.is_empty()
+        || status.current_user.as_ref().is_some_and(|user| {
+            status
+                .principals
+                .iter()
+                .any(|principal| principal == user || principal == "ALL")
+        });
+
+    let outcome = if !findings.is_empty() {
+        CheckOutcome::fail(findings.join("; ")).with_remediation(remediations.join("; "))
+    } else if !status.sudoers_readable {
+        CheckOutcome::warn(format!(
+            "{} could only be read as root; the current user is {}",
+            SUDOERS,
+            if is_admin {
+                format!("in the {} group", status.admin_groups.join(", "))
+            } else {
+                "not in an administrative group".to_string()
+            }
+        ))
+    } else {
+        CheckOutcome::pass(format!(
+            "sudo asks for a password, rules grant {}",
+            names(status.principals.iter())
+        ))
+    };
+    let outcome = outcome.with_value(if is_admin { "admin" } else { "standard" });
+    let outcome = if evidence.is_empty() {
+        outcome
+    } else {
+        outcome.with_evidence(Evidence::file(SUDOERS, &evidence))
+    };
+
+    Ok(outcome.with_details(&status))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn names<'a>(principals: impl Iterator<Item = &'a String>) -> String {
+    let mut names: Vec<&str> = Vec::new();
+    for principal in principals {
+        if !names.contains(&principal.as_str()) {
+            names.push(principal);
+        }
+    }
+    names.join(", ")
+}
+
+/// The administrative groups `user` belongs to, either as a listed member
+/// in /etc/group or through their primary group.
+#[cfg(any(target_os = "linux", test))]
+fn admin_groups(ctx: &CheckContext, user: &str) -> Vec<String> {
+    let primary_gid = ctx.read_file("/etc/passwd").ok().and_then(|passwd| {
+        passwd.lines().find_map(|entry| {
+            let fields: Vec<&str> = entry.split(':').collect();
+            if fields.first() != Some(&user) {
+                return None;
+            }
+            fields.get(3).map(|gid| gid.to_string())
+        })
+    });
+
+    let Ok(groups) = ctx.read_file("/etc/group") else {
+        return Vec::new();
+    };
+    groups
+        .lines()
+        .filter_map(|entry| {
+            let fields: Vec<&str> = entry.split(':').collect();
+            let [name, _, gid, members] = fields[..] else {
+                return None;
+            };
+            let member = members.split(',').any(|member| member.trim() == user)
+                || primary_gid.as_deref() == Some(gid);
+            (ADMIN_GROUPS.contains(&name) && member).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// A user specification before its aliases are expanded.
+#[cfg(any(target_os = "linux", test))]
+struct Spec {
+    users: Vec<String>,
+    commands: Vec<SudoCommand>,
+    source: String,
+    line: String,
+}
+
+/// The parts of a sudoers file and its includes that decide who can
+/// escalate.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Default)]
+struct Sudoers {
+    user_aliases: HashMap<String, Vec<String>>,
+    command_aliases: HashMap<String, Vec<String>>,
+    specs: Vec<Spec>,
+    authenticate_disabled: bool,
+}
+
+#[cfg(any(target_os = "linux", test))]
+impl Sudoers {
+    fn read(&mut self, ctx: &CheckContext, path: &str, depth: usize) -> io::Result<()> {
+        let contents = ctx.read_file(path)?;
+        for (number, line) in logical_lines(&contents) {
+            if let Some(target) = include(&line, "includedir") {
+                if depth < MAX_INCLUDE_DEPTH {
+                    for file in include_dir(ctx, &resolve(path, target)) {
+                        // Unreadable includes are skipped, as sudo does
+                        let _ = self.read(ctx, &file, depth + 1);
+                    }
+                }
+            } else if let Some(target) = include(&line, "include") {
+                if depth < MAX_INCLUDE_DEPTH {
+                    let _ = self.read(ctx, &resolve(path, target), depth + 1);
+                }
+            } else if !line.starts_with('#') {
+                self.parse(&line, format!("{}:{}", path, number));
+            }
+        }
+        Ok(())
+    }
+
+    fn parse(&mut self, line: &str, source: String) {
+        let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        match keyword {
+            "User_Alias" => parse_aliases(rest, &mut self.user_aliases),
+            "Cmnd_Alias" | "Cmd_Alias" => parse_aliases(rest, &mut self.command_aliases),
+            "Host_Alias" | "Runas_Alias" => {}
+            _ if keyword.starts_with("Defaults") => {
+                if rest
+                    .split(',')
+                    .any(|option| option.trim() == "!authenticate")
+                {
+                    self.authenticate_disabled = true;
+                }
+            }
+            _ => {
+                if let Some(spec) = parse_spec(line) {
+                    self.specs.push(Spec { source, ..spec });
+                }
+            }
+        }
+    }
+
+    /// The user specifications with their user and command aliases expanded.
+    fn rules(&self) -> Vec<SudoRule> {
+        self.specs
+            .iter()
+            .map(|spec| SudoRule {
+                principals: spec
+                    .users
+                    .iter()
+                    .flat_map(|user| expand(user, &self.user_aliases, 0))
+                    .collect(),
+                commands: spec
+                    .commands
+                    .iter()
+                    .flat_map(|command| {
+                        expand(&command.command, &self.command_aliases, 0)
+                            .into_iter()
+                            .map(|expanded| SudoCommand {
+                                command: expanded,
+                                ..command.clone()
+                            })
+                    })
+                    .collect(),
+                source: spec.source.clone(),
+                line: spec.line.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Lines with backslash continuations joined, each with the number of the
+/// line it starts on.
+#[cfg(any(target_os = "linux", test))]
+fn logical_lines(contents: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    for (index, line) in contents.lines().enumerate() {
+        let (number, mut joined) = current.take().unwrap_or((index + 1, String::new()));
+        let line = line.trim();
+        match line.strip_suffix('\\') {
+            Some(line) => {
+                joined.push_str(line);
+                joined.push(' ');
+                current = Some((number, joined));
+            }
+            None => {
+                joined.push_str(line);
+                if !joined.trim().is_empty() {
+                    lines.push((number, joined.trim().to_string()));
+                }
+            }
+        }
+    }
+    lines.extend(current.map(|(number, line)| (number, line.trim().to_string())));
+    lines
+}
+
+/// The target of `@include` or the older `#include` spelling.
+#[cfg(any(target_os = "linux", test))]
+fn include<'a>(line: &'a str, directive: &str) -> Option<&'a str> {
+    let rest = line
+        .strip_prefix('@')
+        .or_else(|| line.strip_prefix('#'))?
+        .strip_prefix(directive)?;
+    rest.starts_with(char::is_whitespace)
+        .then(|| rest.trim().trim_matches('"'))
+}
+
+/// Relative includes are resolved against the including file's directory.
+#[cfg(any(target_os = "linux", test))]
+fn resolve(from: &str, target: &str) -> String {
+    if target.starts_with('/') {
+        return target.to_string();
+    }
+    let dir = from.rsplit_once('/').map_or("", |(dir, _)| dir);
+    format!("{}/{}", dir, target)
+}
+
+/// Files sudo reads from an `@includedir`: those without a `.` or a
+/// trailing `~`, in lexical order.
+#[cfg(any(target_os = "linux", test))]
+fn include_dir(ctx: &CheckContext, dir: &str) -> Vec<String> {
+    let mut files: Vec<String> = std::fs::read_dir(ctx.path(dir))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.contains('.') && !name.ends_with('~'))
+        .map(|name| format!("{}/{}", dir.trim_end_matches('/'), name))
+        .collect();
+    files.sort();
+    files
+}
+
+/// `NAME = a, b : OTHER = c` alias definitions.
+#[cfg(any(target_os = "linux", test))]
+fn parse_aliases(definitions: &str, aliases: &mut HashMap<String, Vec<String>>) {
+    for definition in definitions.split(':') {
+        if let Some((name, members)) = definition.split_once('=') {
+            aliases.insert(name.trim().to_string(), split_list(members));
+        }
+    }
+}
+
+/// Splits a comma separated list, keeping commas inside parentheses.
+#[cfg(any(target_os = "linux", test))]
+fn split_list(list: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut depth = 0;
+    for c in list.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(item.trim().to_string());
+                item.clear();
+                continue;
+            }
+            _ => {}
+        }
+        item.push(c);
+    }
+    items.push(item.trim().to_string());
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+/// `users hosts = (runas) TAG: command, ...`. Tags and the runas list
+/// carry over to the commands that follow them.
+#[cfg(any(target_os = "linux", test))]
+fn parse_spec(line: &str) -> Option<Spec> {
+    let (left, right) = line.split_once('=')?;
+    let left = left.replace(", ", ",");
+    let mut left = left.split_whitespace();
+    let users = split_list(left.next()?);
+    left.next()?;
+
+    let mut runas = None;
+    let mut nopasswd = false;
+    let mut commands = Vec::new();
+    for item in split_list(right) {
+        let mut item = item.as_str();
+        if let Some(rest) = item.strip_prefix('(')
+            && let Some((list, rest)) = rest.split_once(')')
+        {
+            runas = Some(list.trim().to_string());
+            item = rest.trim();
+        }
+        while let Some((tag, rest)) = item.split_once(':') {
+            if tag.is_empty() || !tag.chars().all(|c| c.is_ascii_uppercase() || c == '_') {
+                break;
+            }
+            match tag {
+                "NOPASSWD" => nopasswd = true,
+                "PASSWD" => nopasswd = false,
+                _ => {}
+            }
+            item = rest.trim();
+        }
+        commands.push(SudoCommand {
+            command: item.to_string(),
+            runas: runas.clone(),
+            nopasswd,
+        });
+    }
+
+    Some(Spec {
+        users,
+        commands,
+        source: String::new(),
+        line: line.to_string(),
+    })
+}
+
+/// Replaces an alias with its members, recursively.
+#[cfg(any(target_os = "linux", test))]
+fn expand(name: &str, aliases: &HashMap<String, Vec<String>>, depth: usize) -> Vec<String> {
+    match aliases.get(name) {
+        Some(members) if depth < MAX_INCLUDE_DEPTH => members
+            .iter()
+            .flat_map(|member| expand(member, aliases, depth + 1))
+            .collect(),
+        _ => vec![name.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    fn context(root: &str, user: &str) -> CheckContext {
+        CheckContext::new(FixtureRunner::new())
+            .with_root(fixture_root(root))
+            .with_env("USER", user)
+    }
+
+    #[test]
+    fn nopasswd_rules_fail() {
+        let outcome = check_linux(&context("linux/sudoers_nopasswd", "alice")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "sudo grants root without a password check: NOPASSWD rules for ubuntu, alice, bob"
+        );
+        assert_eq!(outcome.value.as_deref(), Some("admin"));
+        assert_eq!(
+            outcome.evidence[0].lines,
+            [
+                "/etc/sudoers.d/90-cloud-init-users:2: ubuntu ALL=(ALL) NOPASSWD:ALL",
+                "/etc/sudoers.d/deploy:4: DEPLOYERS ALL = (root) NOPASSWD: RESTART",
+            ]
+        );
+
+        let status: SudoStatus = serde_json::from_value(*outcome.details.unwrap()).unwrap();
+        assert_eq!(
+            status.principals,
+            ["root", "%admin", "%sudo", "ubuntu", "alice", "bob"]
+        );
+        assert_eq!(status.admin_groups, ["sudo"]);
+        assert_eq!(
+            status.rules[4].commands[1],
+            SudoCommand {
+                command: "/usr/bin/systemctl reload nginx".to_string(),
+                runas: Some("root".to_string()),
+                nopasswd: true,
+            }
+        );
+    }
+
+    #[test]
+    fn password_protected_rules_pass() {
+        let outcome = check_linux(&context("linux/sudoers_default", "dave")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.message,
+            "sudo asks for a password, rules grant root, %wheel"
+        );
+        assert_eq!(outcome.value.as_deref(), Some("standard"));
+    }
+
+    #[test]
+    fn rules_for_every_user_fail_without_claiming_no_password() {
+        let outcome = check_linux(&context("linux/sudoers_everyone", "dave")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(outcome.message, "sudo grants root to every user");
+        assert_eq!(
+            outcome.remediation.as_deref(),
+            Some("Limit sudo rules to an administrative group such as sudo or wheel with `visudo`")
+        );
+        assert_eq!(
+            outcome.evidence[0].lines,
+            ["/etc/sudoers:5: %users ALL=(ALL) ALL"]
+        );
+    }
+
+    #[test]
+    fn missing_sudoers_means_sudo_is_not_installed() {
+        let outcome = check_linux(&context("linux/empty", "alice")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("not installed"));
+    }
+
+    #[test]
+    fn parses_tags_runas_and_continuations() {
+        let lines = logical_lines(
+            "%ops ALL = (root, www-data) \\\n    NOPASSWD: /bin/ls, PASSWD: /bin/rm\n",
+        );
+        assert_eq!(lines.len(), 1);
+
+        let spec = parse_spec(&lines[0].1).unwrap();
+        assert_eq!(spec.users, ["%ops"]);
+        assert_eq!(
+            spec.commands
+                .iter()
+                .map(|command| (command.command.as_str(), command.nopasswd))
+                .collect::<Vec<_>>(),
+            [("/bin/ls", true), ("/bin/rm", false)]
+        );
+        assert_eq!(spec.commands[1].runas.as_deref(), Some("root, www-data"));
+    }
+
+    #[test]
+    fn recognises_both_include_spellings() {
+        assert_eq!(
+            include("@includedir /etc/sudoers.d", "includedir"),
+            Some("/etc/sudoers.d")
+        );
+        assert_eq!(include("#includedir /etc/sudoers.d", "include"), None);
+        assert_eq!(
+            include("#include sudoers.local", "include"),
+            Some("sudoers.local")
+        );
+        assert_eq!(
+            resolve("/etc/sudoers", "sudoers.local"),
+            "/etc/sudoers.local"
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use super::disk_encryption::{block_devices, read_mounts};
+#[cfg(any(target_os = "linux", test))]
+use serde::{Deserialize, Serialize};
+
+pub struct SwapCheck;
+
+/// An active swap area, or the hibernation resume target.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SwapArea {
+    pub path: String,
+    /// `partition`, `file` or `zram`.
+    pub kind: String,
+    /// The block device the swap is written to.
+    pub device: Option<String>,
+    pub encryption: Option<String>,
+    /// Whether the kernel writes the hibernation image here.
+    pub resume: bool,
+}
+
+#[cfg(any(target_os = "linux", test))]
+impl SwapArea {
+    /// zram swap lives in compressed memory, so it never reaches a disk.
+    fn is_protected(&self) -> bool {
+        self.kind == "zram" || self.encryption.is_some()
+    }
+}
+
+#[async_trait]
+impl SecurityCheck for SwapCheck {
+    fn id(&self) -> &'static str {
+        "swap_encryption"
+    }
+
+    fn title(&self) -> &'static str {
+        "Swap Encryption Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying swap and hibernation images are encrypted"
+    }
+
+    fn category(&self) -> Category {
+        Category::Encryption
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Swap encryption detection is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let swaps = ctx
+        .read_file("/proc/swaps")
+        .map_err(|e| CheckOutcome::error(format!("Could not read /proc/swaps: {}", e)))?;
+    // MAJ:MIN of the resume device, "0:0" when hibernation is not set up
+    let resume = ctx
+        .read_file("/sys/power/resume")
+        .ok()
+        .map(|number| number.trim().to_string())
+        .filter(|number| !number.is_empty() && number != "0:0");
+
+    let entries: Vec<(&str, &str)> = swaps
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some((fields.next()?, fields.next()?))
+        })
+        .collect();
+    let mut evidence = vec![Evidence::file(
+        "/proc/swaps",
+        &swaps
+            .lines()
+            .skip(1)
+            .map(str::to_string)
+            .collect::<Vec<_>>(),
+    )];
+    if let Some(number) = &resume {
+        evidence.push(Evidence::file(
+            "/sys/power/resume",
+            std::slice::from_ref(number),
+        ));
+    }
+
+    if entries.is_empty() && resume.is_none() {
+        return Ok(with_evidence(
+            CheckOutcome::pass("No swap is in use").with_value("none"),
+            evidence,
+        ));
+    }
+
+    let (devices, _) = block_devices(ctx)?;
+    let mounts = if entries.iter().any(|(_, kind)| *kind == "file") {
+        read_mounts(ctx)?
+    } else {
+        Vec::new()
+    };
+
+    let mut areas = Vec::new();
+    for (path, kind) in entries {
+        let device = if kind == "file" {
+            // The swap file is encrypted if the filesystem it lives on is
+            mounts
+                .iter()
+                .filter(|mount| contains(&mount.mountpoint, path))
+                .max_by_key(|mount| mount.mountpoint.len())
+                .map(|mount| mount.source.clone())
+        } else {
+            Some(path.to_string())
+        };
+        let backing = device.as_ref().and_then(|device| devices.get(device));
+
+        areas.push(SwapArea {
+            path: path.to_string(),
+            kind: if path.starts_with("/dev/zram") {
+                "zram".to_string()
+            } else {
+                kind.to_string()
+            },
+            device: backing.map_or(device, |backing| Some(backing.path.clone())),
+            encryption: backing.and_then(|backing| backing.encryption.clone()),
+            resume: resume.is_some()
+                && backing.and_then(|backing| backing.number.as_ref()) == resume.as_ref(),
+        });
+    }
+
+    // Hibernation can target a partition that is not currently used as swap
+    if let Some(number) = &resume
+        && !areas.iter().any(|area| area.resume)
+    {
+        let backing = devices
+            .values()
+            .find(|backing| backing.number.as_ref() == Some(number));
+        let path = backing.map_or_else(|| number.clone(), |backing| backing.path.clone());
+        areas.push(SwapArea {
+            device: Some(path.clone()),
+            path,
+            kind: "partition".to_string(),
+            encryption: backing.and_then(|backing| backing.encryption.clone()),
+            resume: true,
+        });
+    }
+
+    Ok(with_evidence(evaluate(&areas), evidence).with_details(&areas))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn evaluate(areas: &[SwapArea]) -> CheckOutcome {
+    let unprotected: Vec<&SwapArea> = areas.iter().filter(|area| !area.is_protected()).collect();
+
+    if let Some(area) = unprotected.iter().find(|area| area.resume) {
+        return CheckOutcome::fail(format!(
+            "The hibernation image is written to unencrypted {}",
+            area.path
+        ))
+        .with_remediation(
+            "Move swap into the encrypted volume, or disable hibernation and encrypt swap with a random key in /etc/crypttab",
+        );
+    }
+    if !unprotected.is_empty() {
+        let paths: Vec<&str> = unprotected.iter().map(|area| area.path.as_str()).collect();
+        return CheckOutcome::fail(format!("Swap is not encrypted: {}", paths.join(", ")))
+            .with_remediation(
+                "Move swap into the encrypted volume, or encrypt it with a random key in /etc/crypttab",
+            );
+    }
+
+    let mut methods: Vec<&str> = Vec::new();
+    for area in areas {
+        let method = area.encryption.as_deref().unwrap_or("zram");
+        if !methods.contains(&method) {
+            methods.push(method);
+        }
+    }
+    let methods = methods.join(", ");
+    CheckOutcome::pass(format!("Swap is encrypted: {}", methods)).with_value(methods)
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn with_evidence(outcome: CheckOutcome, evidence: Vec<Evidence>) -> CheckOutcome {
+    evidence
+        .into_iter()
+        .fold(outcome, CheckOutcome::with_evidence)
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn contains(mountpoint: &str, path: &str) -> bool {
+    mountpoint == "/" || path.starts_with(&format!("{}/", mountpoint.trim_end_matches('/')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    const LSBLK: &str = "lsblk -J -o NAME,KNAME,PATH,MAJ:MIN,TYPE,FSTYPE,RM,HOTPLUG";
+
+    fn context(lsblk: &str, root: &str) -> CheckContext {
+        CheckContext::new(FixtureRunner::new().with_output(LSBLK, lsblk))
+            .with_root(fixture_root(root))
+    }
+
+    fn areas(outcome: &CheckOutcome) -> Vec<SwapArea> {
+        serde_json::from_value(*outcome.details.clone().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn swap_inside_luks_resolves_device_mapper_names() {
+        let ctx = context(
+            include_str!("fixtures/linux/lsblk_json_luks.txt"),
+            "linux/disks_luks",
+        );
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("LUKS"));
+
+        let areas = areas(&outcome);
+        assert_eq!(areas.len(), 1);
+        assert_eq!(areas[0].path, "/dev/dm-3");
+        assert_eq!(areas[0].device.as_deref(), Some("/dev/mapper/vg-swap"));
+        assert!(areas[0].resume);
+    }
+
+    #[test]
+    fn unencrypted_resume_partition_fails() {
+        let ctx = context(
+            include_str!("fixtures/linux/lsblk_json_mixed.txt"),
+            "linux/disks_mixed",
+        );
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "The hibernation image is written to unencrypted /dev/sda3"
+        );
+
+        let zram = areas(&outcome).into_iter().find(|area| area.kind == "zram");
+        assert!(zram.unwrap().is_protected());
+    }
+
+    #[test]
+    fn swap_file_follows_its_filesystem() {
+        let ctx = context(
+            include_str!("fixtures/linux/lsblk_json_mixed.txt"),
+            "linux/swap_file",
+        );
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(outcome.message, "Swap is not encrypted: /data/swapfile");
+
+        let areas = areas(&outcome);
+        assert_eq!(areas[0].device.as_deref(), Some("/dev/mapper/luks-9a3e"));
+        assert_eq!(areas[0].encryption.as_deref(), Some("LUKS"));
+        assert_eq!(areas[1].device.as_deref(), Some("/dev/sda2"));
+    }
+
+    #[test]
+    fn no_swap_passes() {
+        let ctx = context("", "linux/swap_none");
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("none"));
+    }
+}
@@ -0,0 +1,275 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use serde::{Deserialize, Serialize};
+
+pub struct SysctlCheck;
+
+/// A kernel setting that deviates from the baseline.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SysctlFinding {
+    pub key: String,
+    pub expected: String,
+    /// `None` when the kernel does not expose the setting.
+    pub actual: Option<String>,
+    pub description: String,
+    /// `fail` or `warn`.
+    pub severity: String,
+}
+
+#[async_trait]
+impl SecurityCheck for SysctlCheck {
+    fn id(&self) -> &'static str {
+        "kernel_hardening"
+    }
+
+    fn title(&self) -> &'static str {
+        "Kernel Hardening Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying kernel settings match the hardening baseline"
+    }
+
+    fn category(&self) -> Category {
+        Category::Access
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Kernel hardening checks are only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+/// A setting from the embedded `sysctl.toml` baseline.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize)]
+struct Setting {
+    key: String,
+    expected: String,
+    #[serde(default)]
+    comparison: Comparison,
+    #[serde(default)]
+    required: bool,
+    #[serde(default)]
+    severity: Severity,
+    description: String,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum Comparison {
+    #[default]
+    Equals,
+    AtLeast,
+    Prefix,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum Severity {
+    #[default]
+    Fail,
+    Warn,
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Deserialize)]
+struct Baseline {
+    settings: Vec<Setting>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn baseline() -> Vec<Setting> {
+    toml::from_str::<Baseline>(include_str!("sysctl.toml"))
+        .expect("valid sysctl baseline")
+        .settings
+}
+
+#[cfg(any(target_os = "linux", test))]
+impl Setting {
+    fn satisfied_by(&self, actual: &str) -> bool {
+        match self.comparison {
+            Comparison::Equals => actual == self.expected,
+            Comparison::AtLeast => match (actual.parse::<i64>(), self.expected.parse::<i64>()) {
+                (Ok(actual), Ok(expected)) => actual >= expected,
+                _ => false,
+            },
+            Comparison::Prefix => actual.starts_with(&self.expected),
+        }
+    }
+
+    fn expectation(&self) -> String {
+        match self.comparison {
+            Comparison::Equals => self.expected.clone(),
+            Comparison::AtLeast => format!("at least {}", self.expected),
+            Comparison::Prefix => format!("starting with {}", self.expected),
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    if !ctx.path("/proc/sys/kernel").exists() {
+        return Err(CheckOutcome::error("Could not read /proc/sys"));
+    }
+
+    let baseline = baseline();
+    let mut findings = Vec::new();
+    let mut lines = Vec::new();
+    for setting in &baseline {
+        let path = format!("/proc/sys/{}", setting.key.replace('.', "/"));
+        let actual = ctx
+            .read_file(&path)
+            .ok()
+            .map(|value| value.trim().to_string());
+
+        let compliant = match &actual {
+            Some(actual) => {
+                lines.push(format!("{} = {}", setting.key, actual));
+                setting.satisfied_by(actual)
+            }
+            None => !setting.required,
+        };
+        if !compliant {
+            findings.push(SysctlFinding {
+                key: setting.key.clone(),
+                expected: setting.expectation(),
+                actual,
+                description: setting.description.clone(),
+                severity: match setting.severity {
+                    Severity::Fail => "fail",
+                    Severity::Warn => "warn",
+                }
+                .to_string(),
+            });
+        }
+    }
+
+    let summary = findings
+        .iter()
+        .map(|finding| {
+            format!(
+                "{} is {} (expected {})",
+                finding.key,
+                finding.actual.as_deref().unwrap_or("unavailable"),
+                finding.expected
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let outcome = if findings.is_empty() {
+        CheckOutcome::pass(format!(
+            "All {} kernel settings match the hardening baseline",
+            baseline.len()
+        ))
+    } else if findings.iter().any(|finding| finding.severity == "fail") {
+        CheckOutcome::fail(format!(
+            "{} kernel settings deviate from the hardening baseline: {}",
+            findings.len(),
+            summary
+        ))
+        .with_remediation(
+            "Set the expected values in a file under /etc/sysctl.d and apply them with `sysctl --system`",
+        )
+    } else {
+        CheckOutcome::warn(format!(
+            "{} kernel settings deviate from the hardening baseline: {}",
+            findings.len(),
+            summary
+        ))
+    };
+
+    Ok(outcome
+        .with_value(format!(
+            "{}/{}",
+            baseline.len() - findings.len(),
+            baseline.len()
+        ))
+        .with_evidence(Evidence::file("/proc/sys", &lines))
+        .with_details(&findings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    fn context(root: &str) -> CheckContext {
+        CheckContext::new(FixtureRunner::new()).with_root(fixture_root(root))
+    }
+
+    #[test]
+    fn baseline_is_valid() {
+        for setting in baseline() {
+            assert!(
+                setting.satisfied_by(&setting.expected),
+                "{} does not accept its own expected value",
+                setting.key
+            );
+        }
+    }
+
+    #[test]
+    fn hardened_kernel_passes() {
+        let outcome = check_linux(&context("linux/sysctl_hardened")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(outcome.value.as_deref(), Some("11/11"));
+    }
+
+    #[test]
+    fn reports_one_finding_per_deviation() {
+        let outcome = check_linux(&context("linux/sysctl_default")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        let findings: Vec<SysctlFinding> =
+            serde_json::from_value(*outcome.details.unwrap()).unwrap();
+        assert_eq!(
+            findings
+                .iter()
+                .map(|finding| (finding.key.as_str(), finding.actual.as_deref()))
+                .collect::<Vec<_>>(),
+            [
+                ("kernel.kptr_restrict", Some("0")),
+                ("kernel.yama.ptrace_scope", None),
+                ("fs.suid_dumpable", Some("2")),
+                ("net.ipv4.ip_forward", Some("1")),
+                ("net.ipv4.conf.all.rp_filter", Some("0")),
+            ]
+        );
+        assert_eq!(findings[0].expected, "at least 1");
+        assert_eq!(findings[3].severity, "warn");
+    }
+
+    #[test]
+    fn forwarding_alone_only_warns() {
+        let outcome = check_linux(&context("linux/sysctl_router")).unwrap();
+
+        assert_eq!(outcome.status, CheckStatus::Warn);
+        assert_eq!(
+            outcome.message,
+            "1 kernel settings deviate from the hardening baseline: net.ipv4.ip_forward is 1 (expected 0)"
+        );
+    }
+}
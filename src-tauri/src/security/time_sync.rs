@@ -0,0 +1,335 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use serde::{Deserialize, Serialize};
+
+pub struct TimeSyncCheck;
+
+/// How the system clock is kept in sync.
+#[cfg(any(target_os = "linux", test))]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct TimeSyncStatus {
+    /// `systemd-timesyncd`, `chrony` or `ntpd`.
+    pub client: Option<String>,
+    /// Whether the kernel considers the clock synchronized, when known.
+    pub synchronized: Option<bool>,
+    /// Local clock minus NTP time, in milliseconds.
+    pub offset_ms: Option<f64>,
+    pub timezone: Option<String>,
+}
+
+#[async_trait]
+impl SecurityCheck for TimeSyncCheck {
+    fn id(&self) -> &'static str {
+        "time_sync"
+    }
+
+    fn title(&self) -> &'static str {
+        "Time Synchronization Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying the system clock is synchronized over NTP"
+    }
+
+    fn category(&self) -> Category {
+        Category::Network
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx);
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Time synchronization detection is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+/// Offsets beyond this make timestamps from the device unreliable.
+#[cfg(any(target_os = "linux", test))]
+const MAX_OFFSET_MS: f64 = 1000.0;
+
+/// NTP clients by the name of their process. The kernel truncates process
+/// names to 15 characters.
+#[cfg(any(target_os = "linux", test))]
+const CLIENTS: &[(&str, &str)] = &[
+    ("systemd-timesyn", "systemd-timesyncd"),
+    ("chronyd", "chrony"),
+    ("ntpd", "ntpd"),
+];
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(ctx: &CheckContext) -> Result<CheckOutcome, CheckOutcome> {
+    let mut status = TimeSyncStatus::default();
+    let mut evidence = Vec::new();
+
+    let processes = ctx.processes();
+    status.client = CLIENTS
+        .iter()
+        .find(|(process, _)| processes.iter().any(|name| name == process))
+        .map(|(_, client)| client.to_string());
+
+    // timedatectl reports the kernel's view from adjtimex(2), whichever
+    // client disciplines the clock
+    if let Ok(output) = ctx.run("timedatectl", &["show"])
+        && output.success()
+    {
+        let properties = parse_properties(&output.stdout);
+        let property = |name: &str| {
+            properties
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        status.synchronized = property("NTPSynchronized").map(|synced| synced == "yes");
+        status.timezone = property("Timezone")
+            .filter(|timezone| !timezone.is_empty())
+            .map(str::to_string);
+
+        let lines: Vec<String> = ["NTP", "NTPSynchronized", "Timezone"]
+            .iter()
+            .filter_map(|name| Some(format!("{}={}", name, property(name)?)))
+            .collect();
+        evidence.push(output.evidence(&lines));
+    }
+    if status.timezone.is_none() {
+        status.timezone = timezone(ctx);
+    }
+
+    if let Some(client) = &status.client
+        && let Some((offset, output)) = client_offset(ctx, client)
+    {
+        status.offset_ms = Some(offset);
+        evidence.push(output);
+    }
+
+    let outcome = match (&status.client, status.synchronized, status.offset_ms) {
+        (None, _, _) => CheckOutcome::fail("No time synchronization service is running")
+            .with_remediation(
+                "Enable systemd-timesyncd with `timedatectl set-ntp true`, or install chrony",
+            ),
+        (Some(client), Some(false), _) => CheckOutcome::warn(format!(
+            "{} is running, but the clock is not synchronized",
+            client
+        ))
+        .with_remediation("Check that the configured NTP servers are reachable"),
+        (Some(client), _, Some(offset)) if offset.abs() > MAX_OFFSET_MS => {
+            CheckOutcome::warn(format!(
+                "The clock is {} off from NTP time, although {} is running",
+                format_offset(offset),
+                client
+            ))
+            .with_remediation("Check that the configured NTP servers are reachable")
+        }
+        (Some(client), _, Some(offset)) => CheckOutcome::pass(format!(
+            "The clock is synchronized by {} (offset {})",
+            client,
+            format_offset(offset)
+        )),
+        (Some(client), _, None) => {
+            CheckOutcome::pass(format!("The clock is synchronized by {}", client))
+        }
+    };
+    let outcome = match &status.client {
+        Some(client) => outcome.with_value(client.as_str()),
+        None => outcome,
+    };
+
+    Ok(evidence
+        .into_iter()
+        .fold(outcome, CheckOutcome::with_evidence)
+        .with_details(&status))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn format_offset(offset_ms: f64) -> String {
+    if offset_ms.abs() >= 1000.0 {
+        format!("{:+.1} s", offset_ms / 1000.0)
+    } else {
+        format!("{:+.3} ms", offset_ms)
+    }
+}
+
+/// `Key=value` lines, as printed by `timedatectl show`.
+#[cfg(any(target_os = "linux", test))]
+fn parse_properties(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// The clock offset as measured by the running client.
+#[cfg(any(target_os = "linux", test))]
+fn client_offset(ctx: &CheckContext, client: &str) -> Option<(f64, Evidence)> {
+    let (program, args): (&str, &[&str]) = match client {
+        "systemd-timesyncd" => ("timedatectl", &["timesync-status"]),
+        "chrony" => ("chronyc", &["tracking"]),
+        _ => ("ntpq", &["-c", "rv"]),
+    };
+    let output = ctx.run(program, args).ok()?;
+    if !output.success() {
+        return None;
+    }
+
+    let (line, offset) = output.stdout.lines().find_map(|line| {
+        let offset = match client {
+            "systemd-timesyncd" => parse_timesyncd_offset(line),
+            "chrony" => parse_chrony_offset(line),
+            _ => parse_ntpq_offset(line),
+        }?;
+        Some((line.trim().to_string(), offset))
+    })?;
+    Some((offset, output.evidence(&[line])))
+}
+
+/// `Offset: -1.234ms` from `timedatectl timesync-status`.
+#[cfg(any(target_os = "linux", test))]
+fn parse_timesyncd_offset(line: &str) -> Option<f64> {
+    let value = line.trim().strip_prefix("Offset:")?.trim();
+    let split = value.find(|c: char| c.is_alphabetic())?;
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.trim().parse().ok()?;
+    let scale = match unit {
+        "s" => 1000.0,
+        "ms" => 1.0,
+        "us" | "µs" => 0.001,
+        "ns" => 0.000001,
+        _ => return None,
+    };
+    Some(number * scale)
+}
+
+/// `System time : 0.000012345 seconds slow of NTP time` from
+/// `chronyc tracking`.
+#[cfg(any(target_os = "linux", test))]
+fn parse_chrony_offset(line: &str) -> Option<f64> {
+    let (key, value) = line.split_once(':')?;
+    if key.trim() != "System time" {
+        return None;
+    }
+    let mut words = value.split_whitespace();
+    let seconds: f64 = words.next()?.parse().ok()?;
+    let sign = match words.nth(1)? {
+        "fast" => 1.0,
+        "slow" => -1.0,
+        _ => return None,
+    };
+    Some(sign * seconds * 1000.0)
+}
+
+/// `offset=-0.123` in the system variables printed by `ntpq -c rv`, in
+/// milliseconds already.
+#[cfg(any(target_os = "linux", test))]
+fn parse_ntpq_offset(line: &str) -> Option<f64> {
+    line.split(',')
+        .find_map(|variable| variable.trim().strip_prefix("offset="))
+        .and_then(|offset| offset.parse().ok())
+}
+
+/// The configured timezone without systemd, from Debian's /etc/timezone or
+/// the zoneinfo file /etc/localtime links to.
+#[cfg(any(target_os = "linux", test))]
+fn timezone(ctx: &CheckContext) -> Option<String> {
+    if let Ok(timezone) = ctx.read_file("/etc/timezone")
+        && !timezone.trim().is_empty()
+    {
+        return Some(timezone.trim().to_string());
+    }
+
+    let target = std::fs::read_link(ctx.path("/etc/localtime")).ok()?;
+    let target = target.to_string_lossy();
+    target
+        .split_once("zoneinfo/")
+        .map(|(_, timezone)| timezone.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, fixture_root};
+
+    #[test]
+    fn chrony_synchronized_passes_with_offset() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(
+                    "timedatectl show",
+                    include_str!("fixtures/linux/timedatectl_show_synchronized.txt"),
+                )
+                .with_output(
+                    "chronyc tracking",
+                    include_str!("fixtures/linux/chronyc_tracking.txt"),
+                ),
+        )
+        .with_root(fixture_root("linux/time_chrony"));
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(
+            outcome.message,
+            "The clock is synchronized by chrony (offset -0.013 ms)"
+        );
+        assert_eq!(
+            outcome.evidence[0].lines,
+            ["NTP=yes", "NTPSynchronized=yes", "Timezone=Europe/Madrid"]
+        );
+
+        let status: TimeSyncStatus = serde_json::from_value(*outcome.details.unwrap()).unwrap();
+        assert_eq!(status.timezone.as_deref(), Some("Europe/Madrid"));
+    }
+
+    #[test]
+    fn unsynchronized_timesyncd_warns() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(
+            "timedatectl show",
+            include_str!("fixtures/linux/timedatectl_show_unsynchronized.txt"),
+        ))
+        .with_root(fixture_root("linux/time_timesyncd"));
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Warn);
+        assert_eq!(outcome.value.as_deref(), Some("systemd-timesyncd"));
+    }
+
+    #[test]
+    fn no_client_fails_and_falls_back_to_localtime() {
+        let ctx =
+            CheckContext::new(FixtureRunner::new()).with_root(fixture_root("linux/time_none"));
+
+        let outcome = check_linux(&ctx).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+
+        let status: TimeSyncStatus = serde_json::from_value(*outcome.details.unwrap()).unwrap();
+        assert_eq!(status.timezone.as_deref(), Some("America/New_York"));
+    }
+
+    #[test]
+    fn parses_client_offsets() {
+        assert_eq!(parse_timesyncd_offset("      Offset: -1.5ms"), Some(-1.5));
+        assert_eq!(parse_timesyncd_offset("      Offset: +250us"), Some(0.25));
+        assert_eq!(
+            parse_chrony_offset("System time     : 0.002000000 seconds fast of NTP time"),
+            Some(2.0)
+        );
+        assert_eq!(
+            parse_ntpq_offset("offset=-0.412, frequency=-11.374, sys_jitter=0.093,"),
+            Some(-0.412)
+        );
+    }
+}
@@ -0,0 +1,544 @@
+use super::{Category, CheckContext, CheckOutcome, Platform, SecurityCheck};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(target_os = "linux", test))]
+use super::Evidence;
+#[cfg(any(target_os = "linux", test))]
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+
+pub struct UpdatesCheck;
+
+/// Pending updates as reported by the package manager.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct UpdateStatus {
+    pub manager: String,
+    pub pending: u32,
+    /// `None` when the package manager does not classify security updates.
+    pub security: Option<u32>,
+    pub last_update: Option<String>,
+    pub days_since_update: Option<i64>,
+}
+
+#[async_trait]
+impl SecurityCheck for UpdatesCheck {
+    fn id(&self) -> &'static str {
+        "os_updates"
+    }
+
+    fn title(&self) -> &'static str {
+        "OS Updates Check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Verifying operating system updates are installed"
+    }
+
+    fn category(&self) -> Category {
+        Category::Updates
+    }
+
+    fn platforms(&self) -> &'static [Platform] {
+        &[Platform::Linux]
+    }
+
+    async fn run(&self, ctx: &CheckContext) -> CheckOutcome {
+        #[cfg(target_os = "linux")]
+        let outcome = check_linux(ctx, chrono::Local::now().fixed_offset());
+        #[cfg(not(target_os = "linux"))]
+        let outcome = {
+            let _ = ctx;
+            Err(CheckOutcome::not_applicable(
+                "Update detection is only available on Linux",
+            ))
+        };
+
+        outcome.unwrap_or_else(|outcome| outcome)
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+struct Pending {
+    manager: &'static str,
+    pending: u32,
+    security: Option<u32>,
+    evidence: Vec<Evidence>,
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn check_linux(
+    ctx: &CheckContext,
+    now: DateTime<FixedOffset>,
+) -> Result<CheckOutcome, CheckOutcome> {
+    let probes = [apt_pending, dnf_pending, zypper_pending, pacman_pending];
+    let mut found = None;
+    for probe in probes {
+        if let Some(pending) = probe(ctx)? {
+            found = Some(pending);
+            break;
+        }
+    }
+    let Some(pending) = found else {
+        return Err(CheckOutcome::not_applicable(
+            "No supported package manager (apt, dnf, zypper, pacman) was found",
+        ));
+    };
+
+    let (last_update, last_update_evidence) = match pending.manager {
+        "apt" => apt_last_update(ctx),
+        "pacman" => pacman_last_update(ctx, now.offset()),
+        _ => rpm_last_update(ctx, now.offset()),
+    };
+    let days_since_update =
+        last_update.map(|last_update| (now.naive_local() - last_update).num_days());
+
+    let status = UpdateStatus {
+        manager: pending.manager.to_string(),
+        pending: pending.pending,
+        security: pending.security,
+        last_update: last_update.map(|last_update| last_update.to_string()),
+        days_since_update,
+    };
+
+    let mut problems = Vec::new();
+    if pending.pending > 0 {
+        problems.push(match pending.security {
+            Some(security) => format!(
+                "{} updates are pending, {} of them security updates",
+                pending.pending, security
+            ),
+            None => format!("{} updates are pending", pending.pending),
+        });
+    }
+    let max_age = i64::from(ctx.policy().max_update_age_days);
+    if let Some(days) = days_since_update.filter(|days| *days > max_age) {
+        problems.push(format!("the last update was installed {} days ago", days));
+    }
+
+    let mut outcome = if problems.is_empty() {
+        CheckOutcome::pass("The system is up to date")
+    } else {
+        let mut message = problems.join(", and ");
+        message[..1].make_ascii_uppercase();
+        if pending.security.is_some_and(|security| security > 0) {
+            CheckOutcome::fail(message)
+        } else {
+            CheckOutcome::warn(message)
+        }
+        .with_remediation(upgrade_hint(pending.manager))
+    }
+    .with_value(pending.manager)
+    .with_details(&status);
+
+    for evidence in pending.evidence.into_iter().chain(last_update_evidence) {
+        outcome = outcome.with_evidence(evidence);
+    }
+    Ok(outcome)
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn upgrade_hint(manager: &str) -> &'static str {
+    match manager {
+        "apt" => "Run `sudo apt update && sudo apt full-upgrade`",
+        "dnf" => "Run `sudo dnf upgrade --refresh`",
+        "zypper" => "Run `sudo zypper patch`",
+        _ => "Run `sudo pacman -Syu`",
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn apt_pending(ctx: &CheckContext) -> Result<Option<Pending>, CheckOutcome> {
+    // `upgrade` keeps back packages that need new dependencies, which is
+    // how new kernels arrive, so simulate `dist-upgrade` instead
+    let Ok(output) = ctx.run("apt-get", &["-s", "dist-upgrade"]) else {
+        return Ok(None);
+    };
+    let output = output.ensure_success()?;
+
+    // Inst libssl3 [3.0.2-0ubuntu1.14] (3.0.2-0ubuntu1.15 Ubuntu:22.04/jammy-security [amd64])
+    // Newly installed dependencies have no current version in brackets.
+    let upgrades: Vec<String> = output
+        .stdout
+        .lines()
+        .filter(|line| {
+            line.starts_with("Inst ")
+                && line
+                    .split_whitespace()
+                    .nth(2)
+                    .is_some_and(|version| version.starts_with('['))
+        })
+        .map(str::to_string)
+        .collect();
+    let security = upgrades
+        .iter()
+        .filter(|line| line.contains("-security"))
+        .count();
+
+    Ok(Some(Pending {
+        manager: "apt",
+        pending: upgrades.len() as u32,
+        security: Some(security as u32),
+        evidence: vec![output.evidence(&upgrades)],
+    }))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn dnf_pending(ctx: &CheckContext) -> Result<Option<Pending>, CheckOutcome> {
+    let Ok(output) = ctx.run("dnf", &["check-update", "--cacheonly", "-q"]) else {
+        return Ok(None);
+    };
+    // 100 means updates are available
+    if !matches!(output.exit_code, Some(0) | Some(100)) {
+        return Err(output.failure());
+    }
+
+    let upgrades: Vec<String> = output
+        .stdout
+        .lines()
+        .take_while(|line| !line.starts_with("Obsoleting"))
+        .filter(|line| line.split_whitespace().count() == 3)
+        .map(str::to_string)
+        .collect();
+
+    let mut evidence = vec![output.evidence(&upgrades)];
+    let security = match ctx.run(
+        "dnf",
+        &["updateinfo", "list", "--security", "--cacheonly", "-q"],
+    ) {
+        Ok(advisories) if advisories.success() => {
+            let lines: Vec<String> = advisories
+                .stdout
+                .lines()
+                .filter(|line| {
+                    line.split_whitespace()
+                        .any(|field| field.ends_with("/Sec.") || field == "security")
+                })
+                .map(str::to_string)
+                .collect();
+            evidence.push(advisories.evidence(&lines));
+            Some(lines.len() as u32)
+        }
+        _ => None,
+    };
+
+    Ok(Some(Pending {
+        manager: "dnf",
+        pending: upgrades.len() as u32,
+        security,
+        evidence,
+    }))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn zypper_pending(ctx: &CheckContext) -> Result<Option<Pending>, CheckOutcome> {
+    let Ok(output) = ctx.run(
+        "zypper",
+        &["--non-interactive", "--no-refresh", "list-updates"],
+    ) else {
+        return Ok(None);
+    };
+    let output = output.ensure_success()?;
+
+    let upgrades: Vec<String> = output
+        .stdout
+        .lines()
+        .filter(|line| line.starts_with("v "))
+        .map(str::to_string)
+        .collect();
+
+    let mut evidence = vec![output.evidence(&upgrades)];
+    let security = match ctx.run(
+        "zypper",
+        &[
+            "--non-interactive",
+            "--no-refresh",
+            "list-patches",
+            "--category",
+            "security",
+        ],
+    ) {
+        Ok(patches) if patches.success() => {
+            let lines: Vec<String> = patches
+                .stdout
+                .lines()
+                .filter(|line| {
+                    let columns: Vec<&str> = line.split('|').map(str::trim).collect();
+                    columns.contains(&"security") && columns.contains(&"needed")
+                })
+                .map(str::to_string)
+                .collect();
+            evidence.push(patches.evidence(&lines));
+            Some(lines.len() as u32)
+        }
+        _ => None,
+    };
+
+    Ok(Some(Pending {
+        manager: "zypper",
+        pending: upgrades.len() as u32,
+        security,
+        evidence,
+    }))
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn pacman_pending(ctx: &CheckContext) -> Result<Option<Pending>, CheckOutcome> {
+    let Ok(output) = ctx.run("pacman", &["-Qu"]) else {
+        return Ok(None);
+    };
+    // pacman exits with 1 and prints nothing when there is nothing to upgrade
+    let nothing_to_upgrade = output.exit_code == Some(1) && output.stdout.trim().is_empty();
+    if !output.success() && !nothing_to_upgrade {
+        return Err(output.failure());
+    }
+
+    let upgrades: Vec<String> = output
+        .stdout
+        .lines()
+        .filter(|line| line.contains(" -> "))
+        .map(str::to_string)
+        .collect();
+
+    Ok(Some(Pending {
+        manager: "pacman",
+        pending: upgrades.len() as u32,
+        security: None,
+        evidence: vec![output.evidence(&upgrades)],
+    }))
+}
+
+/// The end of the last apt transaction that upgraded packages.
+#[cfg(any(target_os = "linux", test))]
+fn apt_last_update(ctx: &CheckContext) -> (Option<NaiveDateTime>, Option<Evidence>) {
+    const HISTORY: &str = "/var/log/apt/history.log";
+    let Ok(history) = ctx.read_file(HISTORY) else {
+        return (None, None);
+    };
+
+    let end_date = history
+        .split("Start-Date:")
+        .filter(|entry| entry.lines().any(|line| line.starts_with("Upgrade:")))
+        .filter_map(|entry| {
+            entry
+                .lines()
+                .find_map(|line| line.strip_prefix("End-Date:"))
+                .map(str::trim)
+        })
+        .last();
+
+    match end_date {
+        Some(end_date) => {
+            let normalized = end_date.split_whitespace().collect::<Vec<_>>().join(" ");
+            let parsed = NaiveDateTime::parse_from_str(&normalized, "%Y-%m-%d %H:%M:%S").ok();
+            let evidence = Evidence::file(HISTORY, &[format!("End-Date: {}", end_date)]);
+            (parsed, Some(evidence))
+        }
+        None => (None, None),
+    }
+}
+
+/// The last package pacman upgraded, in the local time of `offset`.
+#[cfg(any(target_os = "linux", test))]
+fn pacman_last_update(
+    ctx: &CheckContext,
+    offset: &FixedOffset,
+) -> (Option<NaiveDateTime>, Option<Evidence>) {
+    const LOG: &str = "/var/log/pacman.log";
+    let Ok(log) = ctx.read_file(LOG) else {
+        return (None, None);
+    };
+
+    // [2024-05-14T09:12:40+0200] [ALPM] upgraded linux (6.8.9.arch1-1 -> 6.9.1.arch1-1)
+    let Some(line) = log.lines().rfind(|line| line.contains("] upgraded ")) else {
+        return (None, None);
+    };
+    let timestamp = line
+        .strip_prefix('[')
+        .and_then(|line| line.split(']').next())
+        .unwrap_or_default();
+    let parsed = DateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S%z")
+        .map(|time| time.with_timezone(offset).naive_local())
+        .or_else(|_| NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M"))
+        .ok();
+
+    (parsed, Some(Evidence::file(LOG, &[line.to_string()])))
+}
+
+/// The most recent package install time in the RPM database, in the local
+/// time of `offset`.
+#[cfg(any(target_os = "linux", test))]
+fn rpm_last_update(
+    ctx: &CheckContext,
+    offset: &FixedOffset,
+) -> (Option<NaiveDateTime>, Option<Evidence>) {
+    let Ok(output) = ctx.run("rpm", &["-qa", "--qf", "%{INSTALLTIME}\\n"]) else {
+        return (None, None);
+    };
+
+    let latest = output
+        .stdout
+        .lines()
+        .filter_map(|line| line.trim().parse::<i64>().ok())
+        .max();
+    let parsed = latest
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .map(|time| time.with_timezone(offset).naive_local());
+    let lines: Vec<String> = latest.iter().map(i64::to_string).collect();
+
+    (parsed, Some(output.evidence(&lines)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::outcome::CheckStatus;
+    use crate::security::{FixtureRunner, Policy, fixture_root};
+
+    /// A time in UTC, so timestamps with an offset convert the same way on
+    /// every machine.
+    fn at(timestamp: &str) -> DateTime<FixedOffset> {
+        NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc()
+            .fixed_offset()
+    }
+
+    fn status(outcome: &CheckOutcome) -> UpdateStatus {
+        serde_json::from_value(*outcome.details.clone().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn apt_counts_security_updates() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(
+            "apt-get -s dist-upgrade",
+            include_str!("fixtures/linux/apt_get_simulate_dist_upgrade.txt"),
+        ))
+        .with_root(fixture_root("linux/apt"));
+
+        let outcome = check_linux(&ctx, at("2024-05-20 12:00:00")).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(
+            outcome.message,
+            "4 updates are pending, 3 of them security updates"
+        );
+        assert_eq!(
+            status(&outcome),
+            UpdateStatus {
+                manager: "apt".to_string(),
+                pending: 4,
+                security: Some(3),
+                last_update: Some("2024-05-14 09:12:40".to_string()),
+                days_since_update: Some(6),
+            }
+        );
+    }
+
+    #[test]
+    fn apt_up_to_date_but_stale_warns() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(
+            "apt-get -s dist-upgrade",
+            "Reading package lists...\n0 upgraded, 0 newly installed, 0 to remove and 0 not upgraded.\n",
+        ))
+        .with_root(fixture_root("linux/apt"));
+
+        let fresh = check_linux(&ctx, at("2024-05-20 12:00:00")).unwrap();
+        assert_eq!(fresh.status, CheckStatus::Pass);
+
+        let stale = check_linux(&ctx, at("2024-07-20 12:00:00")).unwrap();
+        assert_eq!(stale.status, CheckStatus::Warn);
+        assert_eq!(stale.message, "The last update was installed 67 days ago");
+
+        let relaxed = ctx.with_policy(Policy {
+            max_update_age_days: 90,
+            ..Policy::default()
+        });
+        let within_policy = check_linux(&relaxed, at("2024-07-20 12:00:00")).unwrap();
+        assert_eq!(within_policy.status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn dnf_reads_check_update_and_advisories() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_exit(
+                    "dnf check-update --cacheonly -q",
+                    100,
+                    include_str!("fixtures/linux/dnf_check_update.txt"),
+                    "",
+                )
+                .with_output(
+                    "dnf updateinfo list --security --cacheonly -q",
+                    "FEDORA-2024-5b1c2a7e1c Moderate/Sec.  openssl-libs-1:3.1.4-3.fc40.x86_64\n",
+                )
+                .with_output("rpm -qa --qf %{INSTALLTIME}\\n", "1715000000\n1715670760\n"),
+        );
+
+        let outcome = check_linux(&ctx, at("2024-05-20 12:00:00")).unwrap();
+        let status = status(&outcome);
+        assert_eq!(outcome.status, CheckStatus::Fail);
+        assert_eq!(status.manager, "dnf");
+        assert_eq!(status.pending, 2);
+        assert_eq!(status.security, Some(1));
+        assert!(status.last_update.is_some());
+    }
+
+    #[test]
+    fn zypper_counts_needed_security_patches() {
+        let ctx = CheckContext::new(
+            FixtureRunner::new()
+                .with_output(
+                    "zypper --non-interactive --no-refresh list-updates",
+                    include_str!("fixtures/linux/zypper_list_updates.txt"),
+                )
+                .with_output(
+                    "zypper --non-interactive --no-refresh list-patches --category security",
+                    include_str!("fixtures/linux/zypper_list_patches_security.txt"),
+                ),
+        );
+
+        let outcome = check_linux(&ctx, at("2024-05-20 12:00:00")).unwrap();
+        let status = status(&outcome);
+        assert_eq!(status.pending, 2);
+        assert_eq!(status.security, Some(1));
+        assert_eq!(status.last_update, None);
+    }
+
+    #[test]
+    fn pacman_without_updates_passes() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_exit("pacman -Qu", 1, "", ""))
+            .with_root(fixture_root("linux/pacman"));
+
+        let outcome = check_linux(&ctx, at("2024-05-20 12:00:00")).unwrap();
+        let status = status(&outcome);
+        assert_eq!(outcome.status, CheckStatus::Pass);
+        assert_eq!(status.security, None);
+        assert_eq!(status.last_update.as_deref(), Some("2024-05-14 07:12:40"));
+        assert_eq!(status.days_since_update, Some(6));
+    }
+
+    #[test]
+    fn pacman_pending_updates_warn() {
+        let ctx = CheckContext::new(FixtureRunner::new().with_output(
+            "pacman -Qu",
+            "linux 6.9.1.arch1-1 -> 6.9.2.arch1-1\nfirefox 126.0-1 -> 126.0.1-1\n",
+        ));
+
+        let outcome = check_linux(&ctx, at("2024-05-20 12:00:00")).unwrap();
+        assert_eq!(outcome.status, CheckStatus::Warn);
+        assert_eq!(outcome.message, "2 updates are pending");
+        assert_eq!(
+            outcome.remediation.as_deref(),
+            Some("Run `sudo pacman -Syu`")
+        );
+    }
+
+    #[test]
+    fn missing_package_manager_is_not_applicable() {
+        let outcome = check_linux(
+            &CheckContext::new(FixtureRunner::new()),
+            at("2024-05-20 12:00:00"),
+        )
+        .unwrap_err();
+        assert_eq!(outcome.status, CheckStatus::NotApplicable);
+    }
+}
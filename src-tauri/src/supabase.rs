@@ -1,24 +1,55 @@
 use crate::device::get_device_id;
+use crate::outbox::{Outbox, SendError};
+use crate::security::{BrowserInstall, CheckOutcome, CheckStatus, OsSupportStatus, UpdateStatus};
 use crate::supabase_credentials;
-use chrono;
 use reqwest::Client;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tauri::State;
 use tauri_plugin_os::{platform, version};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Default)]
 pub struct SecurityReport {
-    antivirus: Option<String>,
-    disk_encryption: Option<String>,
-    screen_lock: Option<u32>,
+    pub checks: BTreeMap<String, CheckOutcome>,
 }
 
-impl Default for SecurityReport {
-    fn default() -> Self {
-        SecurityReport {
-            antivirus: None,
-            disk_encryption: None,
-            screen_lock: None,
-        }
+impl SecurityReport {
+    /// The detected value of a compliant check, e.g. the antivirus name.
+    fn value(&self, id: &str) -> Option<String> {
+        self.checks
+            .get(id)
+            .filter(|outcome| outcome.is_compliant())
+            .and_then(|outcome| outcome.value.clone())
+    }
+
+    /// The detected value of a check whatever its status, e.g. the name of a
+    /// firewall that is installed but misconfigured.
+    fn detected(&self, id: &str) -> Option<String> {
+        self.checks
+            .get(id)
+            .and_then(|outcome| outcome.value.clone())
+    }
+
+    /// Whether a check passed, or `None` if it could not tell, e.g. because
+    /// it does not apply to this platform.
+    fn compliant(&self, id: &str) -> Option<bool> {
+        self.checks
+            .get(id)
+            .filter(|outcome| {
+                matches!(
+                    outcome.status,
+                    CheckStatus::Pass | CheckStatus::Warn | CheckStatus::Fail
+                )
+            })
+            .map(CheckOutcome::is_compliant)
+    }
+
+    /// The structured details of a check, if it reported any.
+    fn details<T: DeserializeOwned>(&self, id: &str) -> Option<T> {
+        let details = self.checks.get(id)?.details.clone()?;
+        serde_json::from_value(*details).ok()
     }
 }
 
@@ -29,77 +60,248 @@ pub struct SupabaseReport {
     user_full_name: String,
     disk_encrypted: bool,
     encryption_type: String,
+    #[serde(default)]
+    swap_encrypted: Option<bool>,
     antivirus_detected: bool,
     antivirus_name: String,
     screen_lock_active: bool,
     screen_lock_time: String,
+    #[serde(default)]
+    firewall_enabled: Option<bool>,
+    /// The firewall that was found, whether or not it is active.
+    #[serde(default)]
+    firewall_name: Option<String>,
+    #[serde(default)]
+    ssh_server_hardened: Option<bool>,
+    #[serde(default)]
+    autologin_disabled: Option<bool>,
+    /// Distinct protocol and port pairs open to the network.
+    #[serde(default)]
+    exposed_ports: Option<u32>,
+    #[serde(default)]
+    pending_updates: Option<u32>,
+    #[serde(default)]
+    pending_security_updates: Option<u32>,
+    #[serde(default)]
+    days_since_update: Option<i64>,
+    /// Installed browsers with their versions, so stale installs can be
+    /// flagged against current releases.
+    #[serde(default)]
+    browsers: Vec<BrowserInstall>,
     operating_system: String,
     os_version: String,
+    /// Linux distribution, e.g. `Ubuntu 22.04.4 LTS`.
+    #[serde(default)]
+    distribution: Option<String>,
+    #[serde(default)]
+    kernel_version: Option<String>,
+    /// `false` when the distribution or kernel no longer receives security
+    /// fixes.
+    #[serde(default)]
+    os_supported: Option<bool>,
     last_check: String,
+    /// `last_check` in UTC, comparable across devices with different
+    /// timezones.
+    #[serde(default)]
+    last_check_utc: String,
+    /// IANA name of the device's timezone, e.g. `Europe/Madrid`.
+    #[serde(default)]
+    timezone: String,
+    #[serde(default)]
+    checks: BTreeMap<String, CheckOutcome>,
 }
 
+impl SupabaseReport {
+    /// Identifies a report in the outbox, so the same run is never queued twice.
+    pub fn key(&self) -> (&str, &str) {
+        (&self.device_id, &self.last_check)
+    }
+}
+
+/// Returns `false` when the report could not be delivered and was queued
+/// for a later retry, and an error when it will not be retried, e.g. because
+/// Supabase is not configured or rejected the report.
 #[tauri::command]
 pub async fn send_security_report(
+    outbox: State<'_, Outbox>,
     user_email: String,
     user_full_name: String,
     report: SecurityReport,
 ) -> Result<bool, String> {
-    let device_id = get_device_id();
-    let credentials = supabase_credentials::get_supabase_credentials()
-        .await?
-        .ok_or_else(|| "Supabase credentials not configured".to_string())?;
+    let report = build_report(user_email, user_full_name, report);
+    match outbox.send(report).await {
+        Ok(()) => Ok(true),
+        Err(SendError::Queued(_)) => Ok(false),
+        Err(SendError::Failed(e)) => Err(e),
+    }
+}
+
+#[tauri::command]
+pub async fn get_last_report(user_email: String) -> Result<Option<SupabaseReport>, String> {
+    fetch_last_report(&user_email).await
+}
+
+pub async fn send_report(
+    user_email: String,
+    user_full_name: String,
+    report: SecurityReport,
+) -> Result<(), String> {
+    deliver(build_report(user_email, user_full_name, report))
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    let supabase_report = SupabaseReport {
-        device_id: device_id.clone(),
-        user_email: user_email.clone(),
+pub fn build_report(
+    user_email: String,
+    user_full_name: String,
+    report: SecurityReport,
+) -> SupabaseReport {
+    let disk_encryption = report.value("disk_encryption");
+    let antivirus = report.value("antivirus");
+    let screen_lock = report.value("screen_lock");
+    let updates = report.details::<UpdateStatus>("os_updates");
+    let os_support = report.details::<OsSupportStatus>("os_support");
+    let now = chrono::Local::now();
+
+    SupabaseReport {
+        device_id: get_device_id(),
+        user_email,
         user_full_name,
-        disk_encrypted: report.disk_encryption.is_some(),
-        encryption_type: report.disk_encryption.unwrap_or_default(),
-        antivirus_detected: report.antivirus.is_some(),
-        antivirus_name: report.antivirus.unwrap_or_default(),
-        screen_lock_active: report.screen_lock.is_some(),
-        screen_lock_time: report.screen_lock.unwrap_or_default().to_string(),
+        disk_encrypted: disk_encryption.is_some(),
+        encryption_type: disk_encryption.unwrap_or_default(),
+        swap_encrypted: report.compliant("swap_encryption"),
+        antivirus_detected: antivirus.is_some(),
+        antivirus_name: antivirus.unwrap_or_default(),
+        screen_lock_active: screen_lock.is_some(),
+        screen_lock_time: screen_lock.unwrap_or_else(|| "0".to_string()),
+        firewall_enabled: report.compliant("firewall"),
+        firewall_name: report.detected("firewall"),
+        ssh_server_hardened: report.compliant("ssh_server"),
+        autologin_disabled: report.compliant("automatic_login"),
+        exposed_ports: report
+            .detected("listening_ports")
+            .and_then(|count| count.parse().ok()),
+        pending_updates: updates.as_ref().map(|updates| updates.pending),
+        pending_security_updates: updates.as_ref().and_then(|updates| updates.security),
+        days_since_update: updates
+            .as_ref()
+            .and_then(|updates| updates.days_since_update),
+        browsers: report
+            .details::<Vec<BrowserInstall>>("browsers")
+            .unwrap_or_default(),
         operating_system: platform().to_string(),
         os_version: version().to_string(),
-        last_check: chrono::Local::now().to_string(),
-    };
+        distribution: os_support
+            .as_ref()
+            .map(|os_support| os_support.distribution.clone()),
+        kernel_version: os_support
+            .as_ref()
+            .and_then(|os_support| os_support.kernel.clone()),
+        os_supported: report.compliant("os_support"),
+        last_check: now.to_string(),
+        last_check_utc: now.with_timezone(&chrono::Utc).to_rfc3339(),
+        timezone: iana_time_zone::get_timezone().unwrap_or_default(),
+        checks: report.checks,
+    }
+}
+
+/// How long a request to Supabase may take before it is abandoned, so a
+/// stalled connection cannot hold up the scheduler.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+fn client() -> Result<Client, String> {
+    Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// Why a report could not be delivered, which decides whether the outbox
+/// retries it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeliveryError {
+    /// The server could not be reached, timed out or failed; a later retry
+    /// may succeed.
+    Transient(String),
+    /// The server rejected this report, so sending it again cannot succeed.
+    Rejected(String),
+    /// Delivery is not configured; no report can be sent until it is.
+    Configuration(String),
+}
+
+impl std::fmt::Display for DeliveryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeliveryError::Transient(e)
+            | DeliveryError::Rejected(e)
+            | DeliveryError::Configuration(e) => f.write_str(e),
+        }
+    }
+}
+
+pub async fn deliver(report: SupabaseReport) -> Result<(), DeliveryError> {
+    let credentials = supabase_credentials::get_supabase_credentials()
+        .await
+        .map_err(DeliveryError::Configuration)?
+        .ok_or_else(|| {
+            DeliveryError::Configuration("Supabase credentials not configured".to_string())
+        })?;
 
-    let client = Client::new();
+    let client = client().map_err(DeliveryError::Configuration)?;
     let response = client
-        .patch(&format!(
+        .patch(format!(
             "{}/rest/v1/security_reports?device_id=eq.{}&on_conflict=user_email,device_id",
-            credentials.url, device_id
+            credentials.url, report.device_id
         ))
         .header("apikey", &credentials.anon_key)
         .header("Authorization", &format!("Bearer {}", credentials.anon_key))
         .header("Content-Type", "application/json")
         .header("Prefer", "return=minimal")
-        .json(&supabase_report)
+        .json(&report)
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| {
+            // A malformed URL is a configuration problem, anything else
+            // happened on the way to the server
+            if e.is_builder() {
+                DeliveryError::Configuration(e.to_string())
+            } else {
+                DeliveryError::Transient(e.to_string())
+            }
+        })?;
 
-    if response.status().is_success() {
-        Ok(true)
-    } else {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        Err(format!("Failed to send report: {}", error_text))
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
     }
+    let error_text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+    let message = format!("Failed to send report: {}", error_text);
+    Err(classify_status(status.as_u16(), message))
 }
 
-#[tauri::command]
-pub async fn get_last_report(user_email: String) -> Result<Option<SupabaseReport>, String> {
+/// Server errors, timeouts and rate limiting are worth retrying. A rejected
+/// key is a configuration problem, any other client error means the report
+/// itself is wrong.
+fn classify_status(status: u16, message: String) -> DeliveryError {
+    match status {
+        401 | 403 => DeliveryError::Configuration(message),
+        408 | 429 | 500.. => DeliveryError::Transient(message),
+        _ => DeliveryError::Rejected(message),
+    }
+}
+
+pub async fn fetch_last_report(user_email: &str) -> Result<Option<SupabaseReport>, String> {
     let credentials = supabase_credentials::get_supabase_credentials()
         .await?
         .ok_or_else(|| "Supabase credentials not configured".to_string())?;
     let device_id = get_device_id();
 
-    let client = Client::new();
+    let client = client()?;
     let response = client
-        .get(&format!("{}/rest/v1/security_reports?device_id=eq.{}&user_email=eq.{}&order=last_check.desc&limit=1", credentials.url, device_id, user_email))
+        .get(format!("{}/rest/v1/security_reports?device_id=eq.{}&user_email=eq.{}&order=last_check.desc&limit=1", credentials.url, device_id, user_email))
         .header("apikey", &credentials.anon_key)
         .header("Authorization", &format!("Bearer {}", credentials.anon_key))
         .send()
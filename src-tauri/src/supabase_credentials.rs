@@ -21,7 +21,7 @@ pub async fn get_supabase_credentials() -> Result<Option<SupabaseCredentials>, S
             Ok(Some(creds))
         }
         Err(e) => {
-            println!("No credentials found in keyring: {:?}", e);
+            eprintln!("No credentials found in keyring: {:?}", e);
             Ok(None)
         }
     }
@@ -0,0 +1,150 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { toast } from "sonner";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Button } from "@/components/ui/button";
+import { Switch } from "@/components/ui/switch";
+
+interface Policy {
+  clamav_max_signature_age_days: number;
+  max_update_age_days: number;
+  allowed_tcp_ports: number[];
+  allowed_udp_ports: number[];
+  removable_storage_allowed: boolean;
+}
+
+const formatPorts = (ports: number[]) => ports.join(", ");
+
+const parsePorts = (value: string) =>
+  value
+    .split(/[\s,]+/)
+    .filter(Boolean)
+    .map(Number)
+    .filter((port) => Number.isInteger(port) && port > 0 && port <= 65535);
+
+export function PolicySettingsForm() {
+  const [saved, setSaved] = useState<Policy | null>(null);
+  const [policy, setPolicy] = useState<Policy>({
+    clamav_max_signature_age_days: 7,
+    max_update_age_days: 30,
+    allowed_tcp_ports: [],
+    allowed_udp_ports: [68, 546, 5353],
+    removable_storage_allowed: true,
+  });
+  const [tcpPorts, setTcpPorts] = useState("");
+  const [udpPorts, setUdpPorts] = useState(
+    formatPorts(policy.allowed_udp_ports),
+  );
+
+  useEffect(() => {
+    invoke<Policy>("get_policy")
+      .then((policy) => {
+        setSaved(policy);
+        setPolicy(policy);
+        setTcpPorts(formatPorts(policy.allowed_tcp_ports));
+        setUdpPorts(formatPorts(policy.allowed_udp_ports));
+      })
+      .catch((error) => console.error(error));
+  }, []);
+
+  const hasChanges =
+    saved !== null && JSON.stringify(saved) !== JSON.stringify(policy);
+
+  return (
+    <form
+      onSubmit={async (e) => {
+        e.preventDefault();
+        try {
+          setSaved(await invoke<Policy>("set_policy", { policy }));
+          toast.success("Policy saved");
+        } catch (error) {
+          console.error(error);
+          toast.error(String(error));
+        }
+      }}
+      className="space-y-4"
+    >
+      <div className="space-y-2">
+        <Label htmlFor="policy-clamav-age">
+          Maximum ClamAV signature age (days)
+        </Label>
+        <Input
+          id="policy-clamav-age"
+          type="number"
+          min={1}
+          value={policy.clamav_max_signature_age_days}
+          onChange={(e) =>
+            setPolicy({
+              ...policy,
+              clamav_max_signature_age_days: Number(e.target.value),
+            })
+          }
+          required
+        />
+      </div>
+      <div className="space-y-2">
+        <Label htmlFor="policy-update-age">
+          Maximum time since the last update (days)
+        </Label>
+        <Input
+          id="policy-update-age"
+          type="number"
+          min={1}
+          value={policy.max_update_age_days}
+          onChange={(e) =>
+            setPolicy({
+              ...policy,
+              max_update_age_days: Number(e.target.value),
+            })
+          }
+          required
+        />
+      </div>
+      <div className="space-y-2">
+        <Label htmlFor="policy-tcp-ports">Allowed TCP ports</Label>
+        <Input
+          id="policy-tcp-ports"
+          placeholder="e.g. 22, 443"
+          value={tcpPorts}
+          onChange={(e) => {
+            setTcpPorts(e.target.value);
+            setPolicy({
+              ...policy,
+              allowed_tcp_ports: parsePorts(e.target.value),
+            });
+          }}
+        />
+      </div>
+      <div className="space-y-2">
+        <Label htmlFor="policy-udp-ports">Allowed UDP ports</Label>
+        <Input
+          id="policy-udp-ports"
+          value={udpPorts}
+          onChange={(e) => {
+            setUdpPorts(e.target.value);
+            setPolicy({
+              ...policy,
+              allowed_udp_ports: parsePorts(e.target.value),
+            });
+          }}
+        />
+      </div>
+      <div className="flex items-center gap-2">
+        <Switch
+          id="policy-removable-storage"
+          checked={policy.removable_storage_allowed}
+          onCheckedChange={(checked) =>
+            setPolicy({ ...policy, removable_storage_allowed: checked })
+          }
+        />
+        <Label htmlFor="policy-removable-storage">
+          Allow USB drives and other removable storage
+        </Label>
+      </div>
+      <Button type="submit" disabled={!hasChanges}>
+        {hasChanges ? "Save Policy" : "No changes"}
+      </Button>
+    </form>
+  );
+}
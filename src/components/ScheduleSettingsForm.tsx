@@ -0,0 +1,80 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { toast } from "sonner";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Button } from "@/components/ui/button";
+import { Switch } from "@/components/ui/switch";
+
+interface ScheduleConfig {
+  enabled: boolean;
+  interval_hours: number;
+}
+
+export function ScheduleSettingsForm() {
+  const [saved, setSaved] = useState<ScheduleConfig | null>(null);
+  const [enabled, setEnabled] = useState(true);
+  const [intervalHours, setIntervalHours] = useState(24);
+
+  useEffect(() => {
+    invoke<ScheduleConfig>("get_schedule_config")
+      .then((config) => {
+        setSaved(config);
+        setEnabled(config.enabled);
+        setIntervalHours(config.interval_hours);
+      })
+      .catch((error) => console.error(error));
+  }, []);
+
+  const hasChanges =
+    saved !== null &&
+    (enabled !== saved.enabled || intervalHours !== saved.interval_hours);
+
+  return (
+    <form
+      onSubmit={async (e) => {
+        e.preventDefault();
+        try {
+          setSaved(
+            await invoke<ScheduleConfig>("set_schedule_config", {
+              enabled,
+              intervalHours,
+            })
+          );
+          toast.success("Schedule saved");
+        } catch (error) {
+          console.error(error);
+          toast.error(String(error));
+        }
+      }}
+      className="space-y-4"
+    >
+      <div className="flex items-center gap-2">
+        <Switch
+          id="schedule-enabled"
+          checked={enabled}
+          onCheckedChange={setEnabled}
+        />
+        <Label htmlFor="schedule-enabled">
+          Run checks and send reports in the background
+        </Label>
+      </div>
+      <div className="space-y-2">
+        <Label htmlFor="schedule-interval">Interval (hours)</Label>
+        <Input
+          id="schedule-interval"
+          type="number"
+          min={1}
+          max={720}
+          value={intervalHours}
+          onChange={(e) => setIntervalHours(Number(e.target.value))}
+          disabled={!enabled}
+          required
+        />
+      </div>
+      <Button type="submit" disabled={!hasChanges}>
+        {hasChanges ? "Save Schedule" : "No changes"}
+      </Button>
+    </form>
+  );
+}
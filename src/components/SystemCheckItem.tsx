@@ -1,14 +1,14 @@
-import { SystemCheck } from "@/contexts/SystemChecksContext";
-
-type CheckStatus = "pending" | "running" | "completed" | "failed";
+import { CheckStatus, SystemCheck } from "@/contexts/SystemChecksContext";
 
 export function SystemCheckItem({ check }: { check: SystemCheck }) {
   const getStatusColor = (status: CheckStatus) => {
     switch (status) {
-      case "completed":
+      case "pass":
         return "bg-green-500/20 text-green-500";
-      case "failed":
+      case "fail":
+      case "error":
         return "bg-red-500/20 text-red-500";
+      case "warn":
       case "running":
         return "bg-yellow-500/20 text-yellow-500";
       default:
@@ -16,6 +16,18 @@ export function SystemCheckItem({ check }: { check: SystemCheck }) {
     }
   };
 
+  const getMessageColor = (status: CheckStatus) => {
+    switch (status) {
+      case "fail":
+      case "error":
+        return "text-red-500";
+      case "warn":
+        return "text-yellow-500";
+      default:
+        return "text-muted-foreground";
+    }
+  };
+
   return (
     <div
       key={check.id}
@@ -34,14 +46,21 @@ export function SystemCheckItem({ check }: { check: SystemCheck }) {
       </div>
       <div className="flex-1">
         <h3 className="font-medium">{check.name}</h3>
-        {check.status === "completed" && (
-          <p className="text-sm text-muted-foreground">
-            {check.successMessage(check.result)}
+        {check.result && (
+          <p
+            className={`text-sm ${getMessageColor(check.status)}`}
+            title={check.result.evidence
+              .map((evidence) =>
+                [`$ ${evidence.command}`, ...evidence.lines].join("\n")
+              )
+              .join("\n\n")}
+          >
+            {check.result.message}
           </p>
         )}
-        {check.status === "failed" && (
-          <p className="text-sm text-red-500 mt-1">
-            {check.errorMessage(check.error)}
+        {check.result?.remediation && (
+          <p className="text-xs text-muted-foreground mt-1">
+            {check.result.remediation}
           </p>
         )}
         {check.status === "pending" && (
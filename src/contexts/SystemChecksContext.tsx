@@ -1,19 +1,66 @@
-import { createContext, useContext, useState, ReactNode } from "react";
-import { HardDrive, ShieldCheck, Wallpaper } from "lucide-react";
+import {
+  createContext,
+  useContext,
+  useState,
+  useEffect,
+  ReactNode,
+} from "react";
+import {
+  HardDrive,
+  Network,
+  RefreshCw,
+  ShieldCheck,
+  Wallpaper,
+} from "lucide-react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 
-export type CheckStatus = "pending" | "running" | "completed" | "failed";
+export type OutcomeStatus =
+  | "pass"
+  | "fail"
+  | "warn"
+  | "error"
+  | "not_applicable";
+
+export type CheckStatus = "pending" | "running" | OutcomeStatus;
+
+export type CheckCategory =
+  | "malware"
+  | "encryption"
+  | "access"
+  | "network"
+  | "updates";
+
+export interface CheckInfo {
+  id: string;
+  title: string;
+  description: string;
+  category: CheckCategory;
+  platforms: string[];
+}
+
+export interface Evidence {
+  command: string;
+  exit_code: number | null;
+  lines: string[];
+}
+
+export interface CheckOutcome {
+  status: OutcomeStatus;
+  message: string;
+  value: string | null;
+  evidence: Evidence[];
+  remediation: string | null;
+  details: unknown;
+}
 
 export interface SystemCheck {
   id: string;
   name: string;
   description: string;
-  successMessage: (result?: string) => string;
-  errorMessage: (error?: string) => string;
   status: CheckStatus;
-  error?: string;
   icon?: ReactNode;
-  result?: string;
+  result?: CheckOutcome;
 }
 
 interface SystemChecksContextType {
@@ -24,83 +71,119 @@ interface SystemChecksContextType {
   resetChecks: () => void;
 }
 
-const securityChecks: (Omit<SystemCheck, "status"> & { cmd: string })[] = [
-  {
-    id: "antivirus",
-    name: "Antivirus Check",
-    description: "Verifying antivirus is installed and running",
-    successMessage: (result?: string) =>
-      `Antivirus is installed and running: ${result || "N/A"}`,
-    errorMessage: (error?: string) =>
-      `Antivirus check failed: ${error || "N/A"}`,
-    icon: <ShieldCheck className="h-5 w-5" />,
-    cmd: "get_antivirus_info",
-  },
-  {
-    id: "disk_encryption",
-    name: "Disk Encryption Check",
-    description: "Verifying disk encryption is enabled",
-    successMessage: (result?: string) =>
-      `Disk encryption is enabled: ${result || "N/A"}`,
-    errorMessage: (error?: string) =>
-      `Disk encryption check failed: ${error || "N/A"}`,
-    icon: <HardDrive className="h-5 w-5" />,
-    cmd: "get_disk_encryption_info",
-  },
-  {
-    id: "screen_lock",
-    name: "Screen Lock Check",
-    description: "Verifying screen lock is enabled",
-    successMessage: (result?: string) =>
-      `Screen lock is enabled: ${result ? `${result} minutes` : "N/A"}`,
-    errorMessage: (error?: string) =>
-      `Screen lock check failed: ${error || "N/A"}`,
-    icon: <Wallpaper className="h-5 w-5" />,
-    cmd: "get_screen_lock_info",
-  },
-];
-
-const initialChecks: Record<string, SystemCheck> = securityChecks.reduce(
-  (acc, check) => {
-    acc[check.id] = { ...check, status: "pending" };
-    return acc;
-  },
-  {} as Record<string, SystemCheck>
-);
+const categoryIcons: Record<CheckCategory, ReactNode> = {
+  malware: <ShieldCheck className="h-5 w-5" />,
+  encryption: <HardDrive className="h-5 w-5" />,
+  access: <Wallpaper className="h-5 w-5" />,
+  network: <Network className="h-5 w-5" />,
+  updates: <RefreshCw className="h-5 w-5" />,
+};
+
+const toSystemCheck = (info: CheckInfo): SystemCheck => ({
+  id: info.id,
+  name: info.title,
+  description: info.description,
+  icon: categoryIcons[info.category] ?? <ShieldCheck className="h-5 w-5" />,
+  status: "pending",
+});
 
 const SystemChecksContext = createContext<SystemChecksContextType | undefined>(
   undefined
 );
 
 export function SystemChecksProvider({ children }: { children: ReactNode }) {
-  const [checks, setChecks] =
-    useState<Record<string, SystemCheck>>(initialChecks);
+  const [availableChecks, setAvailableChecks] = useState<CheckInfo[]>([]);
+  const [checks, setChecks] = useState<Record<string, SystemCheck>>({});
   const [isRunning, setIsRunning] = useState(false);
   const [timeTaken, setTimeTaken] = useState<number | null>(null);
 
+  const initialChecks = (infos: CheckInfo[]) =>
+    infos.reduce((acc, info) => {
+      acc[info.id] = toSystemCheck(info);
+      return acc;
+    }, {} as Record<string, SystemCheck>);
+
+  useEffect(() => {
+    invoke<CheckInfo[]>("list_checks")
+      .then((infos) => {
+        setAvailableChecks(infos);
+        setChecks(initialChecks(infos));
+      })
+      .catch((error) => console.error(error));
+  }, []);
+
+  // Scheduled runs happen in the background, so mirror their progress here
+  useEffect(() => {
+    const unlisteners = [
+      listen("security-checks-started", () => {
+        setIsRunning(true);
+        setTimeTaken(null);
+        setChecks((prevChecks) =>
+          Object.fromEntries(
+            Object.entries(prevChecks).map(([id, check]) => [
+              id,
+              { ...check, status: "running", result: undefined },
+            ])
+          )
+        );
+      }),
+      listen<{ id: string; outcome: CheckOutcome }>(
+        "security-check-completed",
+        ({ payload }) =>
+          setChecks((prevChecks) =>
+            prevChecks[payload.id]
+              ? {
+                  ...prevChecks,
+                  [payload.id]: {
+                    ...prevChecks[payload.id],
+                    status: payload.outcome.status,
+                    result: payload.outcome,
+                  },
+                }
+              : prevChecks
+          )
+      ),
+      listen("security-checks-finished", () => setIsRunning(false)),
+    ];
+
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((fn) => fn()));
+    };
+  }, []);
+
   const runChecks = async () => {
     setIsRunning(true);
     setChecks(
-      securityChecks.reduce((acc, check) => {
-        acc[check.id] = { ...check, status: "running" };
+      availableChecks.reduce((acc, info) => {
+        acc[info.id] = { ...toSystemCheck(info), status: "running" };
         return acc;
       }, {} as Record<string, SystemCheck>)
     );
     setTimeTaken(null);
     const startTime = Date.now();
     await Promise.all(
-      securityChecks.map((check) =>
-        invoke(check.cmd).then((result: unknown) =>
-          setChecks((prevChecks) => ({
-            ...prevChecks,
-            [check.id]: {
-              ...prevChecks[check.id],
-              status: !!result ? "completed" : "failed",
-              result: result as string,
-              error: result ? undefined : "Check failed",
-            },
-          }))
-        )
+      availableChecks.map((info) =>
+        invoke<CheckOutcome>("run_check", { id: info.id })
+          .catch(
+            (error): CheckOutcome => ({
+              status: "error",
+              message: String(error),
+              value: null,
+              evidence: [],
+              remediation: null,
+              details: null,
+            })
+          )
+          .then((result) =>
+            setChecks((prevChecks) => ({
+              ...prevChecks,
+              [info.id]: {
+                ...prevChecks[info.id],
+                status: result.status,
+                result,
+              },
+            }))
+          )
       )
     );
     const endTime = Date.now();
@@ -109,7 +192,7 @@ export function SystemChecksProvider({ children }: { children: ReactNode }) {
   };
 
   const resetChecks = () => {
-    setChecks(initialChecks);
+    setChecks(initialChecks(availableChecks));
     setTimeTaken(null);
   };
 
@@ -1,4 +1,11 @@
-import { createContext, useContext, useState, ReactNode } from "react";
+import {
+  createContext,
+  useContext,
+  useEffect,
+  useState,
+  ReactNode,
+} from "react";
+import { invoke } from "@tauri-apps/api/core";
 
 interface UserCredentials {
   userName: string;
@@ -26,6 +33,14 @@ export function UserCredentialsProvider({ children }: { children: ReactNode }) {
     return savedCredentials ? JSON.parse(savedCredentials) : defaultCredentials;
   });
 
+  // The background scheduler sends reports on the user's behalf
+  useEffect(() => {
+    invoke("set_report_identity", {
+      userEmail: credentials.userEmail,
+      userFullName: credentials.userName,
+    }).catch((error) => console.error(error));
+  }, [credentials.userEmail, credentials.userName]);
+
   const value = {
     credentials,
     setCredentials: (newCredentials: UserCredentials) => {
@@ -8,6 +8,8 @@ import { Button } from "@/components/ui/button";
 import { SystemCheckItem } from "@/components/SystemCheckItem";
 import { UserInfoForm } from "@/components/UserInfoForm";
 import { SupabaseCredentialsForm } from "@/components/SupabaseCredentialsForm";
+import { ScheduleSettingsForm } from "@/components/ScheduleSettingsForm";
+import { PolicySettingsForm } from "@/components/PolicySettingsForm";
 import {
   Collapsible,
   CollapsibleContent,
@@ -15,6 +17,7 @@ import {
 } from "@radix-ui/react-collapsible";
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { useUserCredentials } from "@/contexts/UserCredentialsContext";
 import { toast } from "sonner";
 import { PageLayout } from "@/components/layout/PageLayout";
@@ -32,6 +35,7 @@ export default function SystemChecksPage() {
   const [isReportSettingsOpen, setIsReportSettingsOpen] = useState(false);
   const [isSendingReport, setIsSendingReport] = useState(false);
   const [lastReportDate, setLastReportDate] = useState<Date | null>(null);
+  const [pendingReports, setPendingReports] = useState(0);
 
   const handleSubmit = async (e: React.FormEvent) => {
     if (!credentials.userEmail || !credentials.userName) {
@@ -51,20 +55,19 @@ export default function SystemChecksPage() {
     const toastId = toast.loading("Sending report...");
 
     try {
-      const report = {
-        antivirus: checks.find((check) => check.id === "antivirus")?.result,
-        disk_encryption: checks.find((check) => check.id === "disk_encryption")
-          ?.result,
-        screen_lock: checks.find((check) => check.id === "screen_lock")?.result,
-      };
-
-      if (!report.antivirus || !report.disk_encryption || !report.screen_lock) {
+      if (checks.some((check) => !check.result)) {
         toast.error("Please run all checks before sending the report", {
           id: toastId,
         });
         return;
       }
 
+      const report = {
+        checks: Object.fromEntries(
+          checks.map((check) => [check.id, check.result])
+        ),
+      };
+
       const response = await invoke<boolean>("send_security_report", {
         userEmail: credentials.userEmail,
         userFullName: credentials.userName,
@@ -74,17 +77,25 @@ export default function SystemChecksPage() {
       if (response) {
         toast.success("Report sent successfully", { id: toastId });
       } else {
-        toast.error("Failed to send report", { id: toastId });
+        toast.warning(
+          "Could not reach the server, the report will be sent later",
+          { id: toastId }
+        );
       }
     } catch (error) {
       console.error(error);
       toast.error(
-        error instanceof Error ? error.message : "Failed to send report",
+        typeof error === "string"
+          ? error
+          : error instanceof Error
+            ? error.message
+            : "Failed to send report",
         { id: toastId }
       );
     } finally {
       setIsSendingReport(false);
       fetchLastReport();
+      fetchPendingReports();
     }
   };
 
@@ -112,21 +123,56 @@ export default function SystemChecksPage() {
 
   const fetchLastReport = async () => {
     try {
-      const lastReport = await invoke<{ last_check: string } | null>(
-        "get_last_report",
-        { userEmail: credentials.userEmail }
-      );
+      const lastReport = await invoke<{
+        last_check: string;
+        last_check_utc: string;
+      } | null>("get_last_report", { userEmail: credentials.userEmail });
       if (lastReport) {
-        setLastReportDate(new Date(lastReport.last_check));
+        setLastReportDate(
+          new Date(lastReport.last_check_utc || lastReport.last_check)
+        );
       }
     } catch (error) {
       console.error(error);
     }
   };
+  const fetchPendingReports = async () => {
+    try {
+      const pending = await invoke<{ rejected?: boolean }[]>(
+        "get_pending_reports"
+      );
+      // Rejected reports are never retried, so they are not waiting to sync
+      setPendingReports(pending.filter((report) => !report.rejected).length);
+    } catch (error) {
+      console.error(error);
+    }
+  };
+
   useEffect(() => {
     fetchLastReport();
   }, [credentials.userEmail]);
 
+  useEffect(() => {
+    fetchPendingReports();
+  }, []);
+
+  useEffect(() => {
+    const unlisteners = [
+      listen("security-report-sent", () => {
+        fetchLastReport();
+        fetchPendingReports();
+      }),
+      listen<string>("security-report-failed", ({ payload }) => {
+        toast.error(`Scheduled report failed: ${payload}`);
+        fetchPendingReports();
+      }),
+    ];
+
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((fn) => fn()));
+    };
+  }, [credentials.userEmail]);
+
   return (
     <PageLayout
       title="System Checks"
@@ -173,6 +219,13 @@ export default function SystemChecksPage() {
                   )}
                 </div>
               )}
+              {pendingReports > 0 && (
+                <span className="text-xs text-yellow-500">
+                  {pendingReports === 1
+                    ? "1 report waiting to sync"
+                    : `${pendingReports} reports waiting to sync`}
+                </span>
+              )}
             </div>
             {timeTaken && (
               <span className="text-xs text-muted-foreground">
@@ -227,6 +280,12 @@ export default function SystemChecksPage() {
                 <div>
                   <SupabaseCredentialsForm />
                 </div>
+                <div>
+                  <ScheduleSettingsForm />
+                </div>
+                <div>
+                  <PolicySettingsForm />
+                </div>
               </div>
             </CollapsibleContent>
           </Collapsible>